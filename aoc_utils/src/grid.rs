@@ -1,6 +1,16 @@
+use std::collections::{HashMap, HashSet};
 use std::fmt;
 use std::hash::{Hash, Hasher};
+use std::ops::{Add, Sub};
 
+use crate::math::pos_mod;
+use crate::search::{bfs, dijkstra, dijkstra_to};
+
+/// A 2D integer point; y grows downwards.
+///
+/// Note that the derived `Ord` compares x first, then y (column-major). For
+/// "reading order" (top-to-bottom, then left-to-right) sort by
+/// `reading_order_key` instead.
 #[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd)]
 pub struct Point {
     pub x: i32,
@@ -16,6 +26,18 @@ impl Point {
         (self.x - other.x).abs() + (self.y - other.y).abs()
     }
 
+    /// King-move distance: max(|dx|, |dy|).
+    pub fn chebyshev(self, other: Point) -> i32 {
+        (self.x - other.x).abs().max((self.y - other.y).abs())
+    }
+
+    /// Squared Euclidean distance, computed in i64 so large deltas don't overflow i32.
+    pub fn dist_sq(self, other: Point) -> i64 {
+        let dx = (self.x as i64) - (other.x as i64);
+        let dy = (self.y as i64) - (other.y as i64);
+        dx * dx + dy * dy
+    }
+
     pub fn neighbors4(self) -> [Point; 4] {
         [
             Point::new(self.x + 1, self.y),
@@ -42,6 +64,78 @@ impl Point {
     pub fn from_rc(row: i32, col: i32) -> Self {
         Self { x: col, y: row }
     }
+
+    /// Sort key for reading order: (y, x), i.e. row first, then column.
+    pub fn reading_order_key(self) -> (i32, i32) {
+        (self.y, self.x)
+    }
+
+    /// Every point within Manhattan distance `r` (inclusive), row by row.
+    pub fn manhattan_disk(self, r: i32) -> impl Iterator<Item = Point> {
+        (-r..=r).flat_map(move |dy| {
+            let w = r - dy.abs();
+            (-w..=w).map(move |dx| Point::new(self.x + dx, self.y + dy))
+        })
+    }
+
+    /// Every point at exactly Manhattan distance `r`: 4r points, or just
+    /// `self` when `r` is 0.
+    pub fn manhattan_ring(self, r: i32) -> impl Iterator<Item = Point> {
+        let center = (r == 0).then_some(self);
+        let edges = (0..r).flat_map(move |i| {
+            [
+                Point::new(self.x + r - i, self.y + i),
+                Point::new(self.x - i, self.y + r - i),
+                Point::new(self.x - r + i, self.y - i),
+                Point::new(self.x + i, self.y - r + i),
+            ]
+        });
+        center.into_iter().chain(edges)
+    }
+
+    /// Multiply both components by `k`.
+    pub fn scale(self, k: i32) -> Self {
+        Self::new(self.x * k, self.y * k)
+    }
+
+    /// Rotate 90 degrees clockwise about the origin, as seen on screen with
+    /// y growing downwards: (x, y) -> (-y, x). Up (0, -1) becomes right (1, 0).
+    pub fn rotate90_cw(self) -> Self {
+        Self::new(-self.y, self.x)
+    }
+
+    /// Rotate 90 degrees counter-clockwise about the origin, as seen on screen
+    /// with y growing downwards: (x, y) -> (y, -x). Right (1, 0) becomes up (0, -1).
+    pub fn rotate90_ccw(self) -> Self {
+        Self::new(self.y, -self.x)
+    }
+
+    /// Turn a heading vector right; same as `rotate90_cw`, so that
+    /// `pos = pos + heading; heading = heading.turn_cw()` reads naturally.
+    pub fn turn_cw(self) -> Self {
+        self.rotate90_cw()
+    }
+
+    /// Turn a heading vector left; same as `rotate90_ccw`.
+    pub fn turn_ccw(self) -> Self {
+        self.rotate90_ccw()
+    }
+}
+
+impl Add for Point {
+    type Output = Point;
+
+    fn add(self, other: Point) -> Point {
+        Point::new(self.x + other.x, self.y + other.y)
+    }
+}
+
+impl Sub for Point {
+    type Output = Point;
+
+    fn sub(self, other: Point) -> Point {
+        Point::new(self.x - other.x, self.y - other.y)
+    }
 }
 
 impl fmt::Debug for Point {
@@ -57,6 +151,236 @@ impl Hash for Point {
     }
 }
 
+/// Compass heading on a grid where y grows downward (North is -y).
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum Direction {
+    North,
+    East,
+    South,
+    West,
+}
+
+impl Direction {
+    /// Parse `U/R/D/L`, `N/E/S/W` or `^/>/v/<`.
+    pub fn from_char(c: char) -> Option<Self> {
+        match c {
+            'U' | 'N' | '^' => Some(Direction::North),
+            'R' | 'E' | '>' => Some(Direction::East),
+            'D' | 'S' | 'v' => Some(Direction::South),
+            'L' | 'W' | '<' => Some(Direction::West),
+            _ => None,
+        }
+    }
+
+    /// Unit step in this direction.
+    pub fn delta(self) -> Point {
+        match self {
+            Direction::North => Point::new(0, -1),
+            Direction::East => Point::new(1, 0),
+            Direction::South => Point::new(0, 1),
+            Direction::West => Point::new(-1, 0),
+        }
+    }
+
+    /// Heading after a quarter turn clockwise.
+    pub fn turn_right(self) -> Self {
+        match self {
+            Direction::North => Direction::East,
+            Direction::East => Direction::South,
+            Direction::South => Direction::West,
+            Direction::West => Direction::North,
+        }
+    }
+
+    /// Heading after a quarter turn counter-clockwise.
+    pub fn turn_left(self) -> Self {
+        self.turn_right().turn_right().turn_right()
+    }
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd)]
+pub struct Point3 {
+    pub x: i32,
+    pub y: i32,
+    pub z: i32,
+}
+
+impl Point3 {
+    pub const fn new(x: i32, y: i32, z: i32) -> Self {
+        Self { x, y, z }
+    }
+
+    pub fn manhattan(self, other: Point3) -> i32 {
+        (self.x - other.x).abs() + (self.y - other.y).abs() + (self.z - other.z).abs()
+    }
+
+    /// The six face-adjacent neighbors.
+    pub fn neighbors6(self) -> [Point3; 6] {
+        [
+            Point3::new(self.x + 1, self.y, self.z),
+            Point3::new(self.x - 1, self.y, self.z),
+            Point3::new(self.x, self.y + 1, self.z),
+            Point3::new(self.x, self.y - 1, self.z),
+            Point3::new(self.x, self.y, self.z + 1),
+            Point3::new(self.x, self.y, self.z - 1),
+        ]
+    }
+
+    /// All 26 neighbors sharing a face, edge or corner.
+    pub fn neighbors26(self) -> [Point3; 26] {
+        let mut out = [self; 26];
+        let mut i = 0;
+        for dx in -1..=1 {
+            for dy in -1..=1 {
+                for dz in -1..=1 {
+                    if dx == 0 && dy == 0 && dz == 0 {
+                        continue;
+                    }
+                    out[i] = Point3::new(self.x + dx, self.y + dy, self.z + dz);
+                    i += 1;
+                }
+            }
+        }
+        out
+    }
+}
+
+impl Add for Point3 {
+    type Output = Point3;
+
+    fn add(self, other: Point3) -> Point3 {
+        Point3::new(self.x + other.x, self.y + other.y, self.z + other.z)
+    }
+}
+
+impl Sub for Point3 {
+    type Output = Point3;
+
+    fn sub(self, other: Point3) -> Point3 {
+        Point3::new(self.x - other.x, self.y - other.y, self.z - other.z)
+    }
+}
+
+impl fmt::Debug for Point3 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "({}, {}, {})", self.x, self.y, self.z)
+    }
+}
+
+impl Hash for Point3 {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.x.hash(state);
+        self.y.hash(state);
+        self.z.hash(state);
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, thiserror::Error)]
+pub enum GridError {
+    #[error("ragged grid: row {row} has length {len}, expected {expected}")]
+    Ragged { row: usize, len: usize, expected: usize },
+}
+
+/// Check that all rows have the same length before using them as a grid.
+/// The error names the first row whose length differs from row 0.
+pub fn grid_from_rows<T>(rows: Vec<Vec<T>>) -> Result<Vec<Vec<T>>, GridError> {
+    let expected = rows.first().map_or(0, |r| r.len());
+    if let Some((row, r)) = rows.iter().enumerate().find(|(_, r)| r.len() != expected) {
+        return Err(GridError::Ragged { row, len: r.len(), expected });
+    }
+    Ok(rows)
+}
+
+/// Number of exposed unit-cube faces: six per cube minus faces shared with
+/// another cube in the set.
+pub fn surface_area(cubes: &HashSet<Point3>) -> usize {
+    cubes
+        .iter()
+        .flat_map(|c| c.neighbors6())
+        .filter(|n| !cubes.contains(n))
+        .count()
+}
+
+/// Like `surface_area`, but only counts faces reachable from outside, i.e.
+/// excluding faces that border fully enclosed air pockets.
+pub fn exterior_surface_area(cubes: &HashSet<Point3>) -> usize {
+    let Some(bounds) = bounding_box_3d(cubes.iter().copied()) else {
+        return 0;
+    };
+    let outside = flood_exterior(cubes, bounds);
+
+    cubes
+        .iter()
+        .flat_map(|c| c.neighbors6())
+        .filter(|n| outside.contains(n))
+        .count()
+}
+
+/// Smallest (min, max) corners enclosing all `cubes`, or None if empty.
+pub fn bounding_box_3d(cubes: impl IntoIterator<Item = Point3>) -> Option<(Point3, Point3)> {
+    cubes.into_iter().fold(None, |acc, c| match acc {
+        None => Some((c, c)),
+        Some((lo, hi)) => Some((
+            Point3::new(lo.x.min(c.x), lo.y.min(c.y), lo.z.min(c.z)),
+            Point3::new(hi.x.max(c.x), hi.y.max(c.y), hi.z.max(c.z)),
+        )),
+    })
+}
+
+/// Every empty cell reachable from outside the `cubes`, within `bounds` padded
+/// by one on each side (so the outside forms one connected shell). Enclosed air
+/// pockets are the empty cells in `bounds` not in the result.
+pub fn flood_exterior(cubes: &HashSet<Point3>, bounds: (Point3, Point3)) -> HashSet<Point3> {
+    let (lo, hi) = (bounds.0 - Point3::new(1, 1, 1), bounds.1 + Point3::new(1, 1, 1));
+    let inside = |p: &Point3| {
+        (lo.x..=hi.x).contains(&p.x) && (lo.y..=hi.y).contains(&p.y) && (lo.z..=hi.z).contains(&p.z)
+    };
+
+    bfs(lo, |p: Point3| {
+        p.neighbors6()
+            .into_iter()
+            .filter(|n| inside(n) && !cubes.contains(n))
+    })
+    .into_keys()
+    .collect()
+}
+
+/// An axis-aligned rectangle of points, inclusive of both corners.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct Rect {
+    pub min: Point,
+    pub max: Point,
+}
+
+impl Rect {
+    /// Bounding box of `points`, or None if there are none.
+    pub fn from_points(points: &[Point]) -> Option<Rect> {
+        let first = *points.first()?;
+        Some(points.iter().fold(Rect { min: first, max: first }, |r, p| Rect {
+            min: Point::new(r.min.x.min(p.x), r.min.y.min(p.y)),
+            max: Point::new(r.max.x.max(p.x), r.max.y.max(p.y)),
+        }))
+    }
+
+    pub fn contains(&self, p: Point) -> bool {
+        (self.min.x..=self.max.x).contains(&p.x) && (self.min.y..=self.max.y).contains(&p.y)
+    }
+
+    pub fn width(&self) -> i32 {
+        self.max.x - self.min.x + 1
+    }
+
+    pub fn height(&self) -> i32 {
+        self.max.y - self.min.y + 1
+    }
+
+    /// Every contained point in row-major order.
+    pub fn iter(&self) -> impl Iterator<Item = Point> {
+        let min = self.min;
+        grid_points(self.width(), self.height()).map(move |p| Point::new(p.x + min.x, p.y + min.y))
+    }
+}
+
 /// Helpers for working with 2D grids stored as Vec<Vec<T>>.
 pub trait GridExt<T> {
     fn width(&self) -> i32;
@@ -64,6 +388,37 @@ pub trait GridExt<T> {
     fn in_bounds(&self, p: Point) -> bool;
     fn get_point(&self, p: Point) -> Option<&T>;
     fn get_point_mut(&mut self, p: Point) -> Option<&mut T>;
+    /// Same as `get_point(Point::from_rc(row, col))`: row is y, col is x.
+    fn get_rc(&self, row: i32, col: i32) -> Option<&T>;
+    /// Same as `get_point_mut(Point::from_rc(row, col))`: row is y, col is x.
+    fn get_rc_mut(&mut self, row: i32, col: i32) -> Option<&mut T>;
+    /// Iterate over every cell mutably together with its Point, row by row.
+    fn iter_points_mut<'a>(&'a mut self) -> impl Iterator<Item = (Point, &'a mut T)>
+    where
+        T: 'a;
+    /// Every in-bounds Point in row-major order; see `grid_points`.
+    fn points(&self) -> impl Iterator<Item = Point>;
+    /// `p.neighbors4()` wrapped around the edges (toroidally) with `pos_mod`.
+    /// Panics on an empty grid, where there is nothing to wrap onto.
+    fn neighbors4_wrapped(&self, p: Point) -> [Point; 4];
+    /// Cell at `p` wrapped onto the grid; only None for an empty grid.
+    fn get_point_wrapped(&self, p: Point) -> Option<&T>;
+    /// Top-left, top-right, bottom-left, bottom-right. Not meaningful for an empty grid.
+    fn corners(&self) -> [Point; 4];
+    /// Every cell on the grid's perimeter in row-major order, without duplicates.
+    fn edge_points(&self) -> Vec<Point>;
+    /// Row `y` as a slice, or None if out of range.
+    fn row(&self, y: i32) -> Option<&[T]>;
+    /// Column `x` from top to bottom; empty if `x` is out of range.
+    fn column(&self, x: i32) -> Vec<&T>;
+    /// Every row, top to bottom.
+    fn rows<'a>(&'a self) -> impl Iterator<Item = &'a [T]>
+    where
+        T: 'a;
+    /// Every column, left to right; see `column`.
+    fn columns<'a>(&'a self) -> impl Iterator<Item = Vec<&'a T>>
+    where
+        T: 'a;
 }
 
 impl<T> GridExt<T> for Vec<Vec<T>> {
@@ -93,6 +448,581 @@ impl<T> GridExt<T> for Vec<Vec<T>> {
         self.get_mut(p.y as usize)
             .and_then(|row| row.get_mut(p.x as usize))
     }
+
+    fn get_rc(&self, row: i32, col: i32) -> Option<&T> {
+        self.get_point(Point::from_rc(row, col))
+    }
+
+    fn get_rc_mut(&mut self, row: i32, col: i32) -> Option<&mut T> {
+        self.get_point_mut(Point::from_rc(row, col))
+    }
+
+    fn iter_points_mut<'a>(&'a mut self) -> impl Iterator<Item = (Point, &'a mut T)>
+    where
+        T: 'a,
+    {
+        self.iter_mut().enumerate().flat_map(|(y, row)| {
+            row.iter_mut()
+                .enumerate()
+                .map(move |(x, cell)| (Point::new(x as i32, y as i32), cell))
+        })
+    }
+
+    fn points(&self) -> impl Iterator<Item = Point> {
+        grid_points(self.width(), self.height())
+    }
+
+    fn neighbors4_wrapped(&self, p: Point) -> [Point; 4] {
+        assert!(self.width() > 0, "neighbors4_wrapped on an empty grid");
+        let (w, h) = (self.width() as i64, self.height() as i64);
+        p.neighbors4().map(|n| {
+            Point::new(pos_mod(n.x as i64, w) as i32, pos_mod(n.y as i64, h) as i32)
+        })
+    }
+
+    fn get_point_wrapped(&self, p: Point) -> Option<&T> {
+        if self.width() == 0 {
+            return None;
+        }
+        let (w, h) = (self.width() as i64, self.height() as i64);
+        self.get_point(Point::new(pos_mod(p.x as i64, w) as i32, pos_mod(p.y as i64, h) as i32))
+    }
+
+    fn corners(&self) -> [Point; 4] {
+        let (w, h) = (self.width(), self.height());
+        [
+            Point::new(0, 0),
+            Point::new(w - 1, 0),
+            Point::new(0, h - 1),
+            Point::new(w - 1, h - 1),
+        ]
+    }
+
+    fn edge_points(&self) -> Vec<Point> {
+        let (w, h) = (self.width(), self.height());
+        let mut out = Vec::new();
+        for y in 0..h {
+            if y == 0 || y == h - 1 {
+                out.extend((0..w).map(|x| Point::new(x, y)));
+            } else {
+                out.push(Point::new(0, y));
+                if w > 1 {
+                    out.push(Point::new(w - 1, y));
+                }
+            }
+        }
+        out
+    }
+
+    fn row(&self, y: i32) -> Option<&[T]> {
+        self.get(usize::try_from(y).ok()?).map(|r| r.as_slice())
+    }
+
+    fn column(&self, x: i32) -> Vec<&T> {
+        (0..self.height()).filter_map(|y| self.get_point(Point::new(x, y))).collect()
+    }
+
+    fn rows<'a>(&'a self) -> impl Iterator<Item = &'a [T]>
+    where
+        T: 'a,
+    {
+        self.iter().map(|r| r.as_slice())
+    }
+
+    fn columns<'a>(&'a self) -> impl Iterator<Item = Vec<&'a T>>
+    where
+        T: 'a,
+    {
+        (0..self.width()).map(|x| self.column(x))
+    }
+}
+
+/// A fixed set of points answering "closest point by Manhattan distance" queries.
+///
+/// Queries scan every point, which is plenty for typical AoC sizes. Ties go to
+/// the smallest point by `Point`'s `Ord`.
+#[derive(Debug, Clone)]
+pub struct NearestIndex {
+    points: Vec<Point>,
+}
+
+impl NearestIndex {
+    /// Build the index; returns None if `points` is empty.
+    pub fn new(points: impl IntoIterator<Item = Point>) -> Option<Self> {
+        let mut points: Vec<Point> = points.into_iter().collect();
+        if points.is_empty() {
+            return None;
+        }
+        points.sort();
+        points.dedup();
+        Some(Self { points })
+    }
+
+    /// The closest indexed point to `p` and its Manhattan distance.
+    pub fn nearest(&self, p: Point) -> (Point, i32) {
+        self.points
+            .iter()
+            .map(|&q| (q, q.manhattan(p)))
+            .min_by_key(|&(_, d)| d)
+            .expect("NearestIndex is never empty")
+    }
+}
+
+/// Every Point in a `width` x `height` area, in row-major order.
+pub fn grid_points(width: i32, height: i32) -> impl Iterator<Item = Point> {
+    (0..height).flat_map(move |y| (0..width).map(move |x| Point::new(x, y)))
+}
+
+/// Cell at `p` in a grid slice, or None if out of bounds.
+fn cell_at<T>(grid: &[Vec<T>], p: Point) -> Option<&T> {
+    if p.x < 0 || p.y < 0 {
+        return None;
+    }
+    grid.get(p.y as usize).and_then(|row| row.get(p.x as usize))
+}
+
+/// BFS over a grid with 4-connectivity: returns distances from `start` to every
+/// reachable cell. Only in-bounds cells where `passable` holds are entered; the
+/// start itself is always included.
+pub fn grid_bfs<T, F>(grid: &[Vec<T>], start: Point, passable: F) -> HashMap<Point, usize>
+where
+    F: Fn(&T) -> bool,
+{
+    bfs(start, |p: Point| {
+        p.neighbors4()
+            .into_iter()
+            .filter(|&nb| cell_at(grid, nb).is_some_and(&passable))
+    })
+}
+
+/// Rotate a rectangular grid clockwise by `quarter_turns % 4` quarter turns.
+pub fn rotate<T: Clone>(grid: &[Vec<T>], quarter_turns: u8) -> Vec<Vec<T>> {
+    let h = grid.len();
+    let w = grid.first().map_or(0, |row| row.len());
+
+    match quarter_turns % 4 {
+        0 => grid.to_vec(),
+        1 => (0..w)
+            .map(|r| (0..h).map(|c| grid[h - 1 - c][r].clone()).collect())
+            .collect(),
+        2 => grid.iter().rev().map(|row| row.iter().rev().cloned().collect()).collect(),
+        _ => (0..w)
+            .map(|r| (0..h).map(|c| grid[c][w - 1 - r].clone()).collect())
+            .collect(),
+    }
+}
+
+/// Every 4-adjacency between two passable cells, listed once as (a, b) with
+/// `b` to the right of or below `a`. Handy for feeding edge-list algorithms.
+pub fn grid_to_edges(grid: &[Vec<char>], passable: impl Fn(char) -> bool) -> Vec<(Point, Point)> {
+    let open = |p: Point| cell_at(grid, p).is_some_and(|&c| passable(c));
+    let mut edges = Vec::new();
+
+    for (y, row) in grid.iter().enumerate() {
+        for x in 0..row.len() {
+            let p = Point::new(x as i32, y as i32);
+            if !open(p) {
+                continue;
+            }
+            for q in [Point::new(p.x + 1, p.y), Point::new(p.x, p.y + 1)] {
+                if open(q) {
+                    edges.push((p, q));
+                }
+            }
+        }
+    }
+
+    edges
+}
+
+/// Length of the longest simple path from `start` to `goal` over non-'#' cells,
+/// or None if `goal` is unreachable.
+///
+/// With `slopes_one_way`, a slope cell (`^ > v <`) may only be left in the
+/// direction it points. Corridors are first contracted into a weighted graph
+/// between junctions (cells with more than two open neighbors, plus `start`
+/// and `goal`), then every simple path through that graph is tried, so this is
+/// only practical when there are few junctions. None if `start` or `goal` is
+/// not an open cell.
+pub fn longest_grid_path(
+    grid: &[Vec<char>],
+    start: Point,
+    goal: Point,
+    slopes_one_way: bool,
+) -> Option<usize> {
+    let open = |p: Point| cell_at(grid, p).is_some_and(|&c| c != '#');
+    if !open(start) || !open(goal) {
+        return None;
+    }
+    let moves = |p: Point| -> Vec<Point> {
+        let forced = match grid[p.y as usize][p.x as usize] {
+            '^' if slopes_one_way => Some(Point::new(p.x, p.y - 1)),
+            '>' if slopes_one_way => Some(Point::new(p.x + 1, p.y)),
+            'v' if slopes_one_way => Some(Point::new(p.x, p.y + 1)),
+            '<' if slopes_one_way => Some(Point::new(p.x - 1, p.y)),
+            _ => None,
+        };
+        match forced {
+            Some(q) => [q].into_iter().filter(|&q| open(q)).collect(),
+            None => p.neighbors4().into_iter().filter(|&q| open(q)).collect(),
+        }
+    };
+
+    let mut junctions: Vec<Point> = vec![start, goal];
+    for (y, row) in grid.iter().enumerate() {
+        for x in 0..row.len() {
+            let p = Point::new(x as i32, y as i32);
+            let exits = p.neighbors4().into_iter().filter(|&q| open(q)).count();
+            if open(p) && exits > 2 && p != start && p != goal {
+                junctions.push(p);
+            }
+        }
+    }
+    let index: HashMap<Point, usize> = junctions.iter().enumerate().map(|(i, &p)| (p, i)).collect();
+
+    // edges[i] = (j, steps) for each corridor from junction i to junction j;
+    // every exit is walked on its own so parallel corridors are all kept
+    let mut edges: Vec<Vec<(usize, usize)>> = vec![Vec::new(); junctions.len()];
+    for (i, &j) in junctions.iter().enumerate() {
+        for first in moves(j) {
+            let (mut prev, mut cur, mut d) = (j, first, 1);
+            loop {
+                if let Some(&k) = index.get(&cur) {
+                    edges[i].push((k, d));
+                    break;
+                }
+                // corridor cells have at most one way forward
+                let Some(next) = moves(cur).into_iter().find(|&q| q != prev) else {
+                    break; // dead end, or a slope pointing back
+                };
+                (prev, cur, d) = (cur, next, d + 1);
+            }
+        }
+    }
+
+    fn longest(cur: usize, goal: usize, visited: &mut [bool], edges: &[Vec<(usize, usize)>]) -> Option<usize> {
+        if cur == goal {
+            return Some(0);
+        }
+        let mut best = None;
+        for &(next, d) in &edges[cur] {
+            if visited[next] {
+                continue;
+            }
+            visited[next] = true;
+            if let Some(rest) = longest(next, goal, visited, edges) {
+                best = best.max(Some(rest + d));
+            }
+            visited[next] = false;
+        }
+        best
+    }
+
+    let mut visited = vec![false; junctions.len()];
+    visited[0] = true;
+    longest(0, 1, &mut visited, &edges)
+}
+
+/// All integer points on the segment from `a` to `b` inclusive (Bresenham),
+/// for any slope. Starts at `a` and ends at `b`.
+pub fn line(a: Point, b: Point) -> Vec<Point> {
+    let dx = (b.x - a.x).abs();
+    let dy = -(b.y - a.y).abs();
+    let sx = (b.x - a.x).signum();
+    let sy = (b.y - a.y).signum();
+    let mut err = dx + dy;
+    let mut cur = a;
+    let mut out = vec![cur];
+
+    while cur != b {
+        let e2 = 2 * err;
+        if e2 >= dy {
+            err += dy;
+            cur.x += sx;
+        }
+        if e2 <= dx {
+            err += dx;
+            cur.y += sy;
+        }
+        out.push(cur);
+    }
+
+    out
+}
+
+/// Points from `a` to `b` inclusive for horizontal, vertical or 45-degree
+/// segments (the usual AoC case); None for any other slope.
+pub fn line_orthogonal_or_diagonal(a: Point, b: Point) -> Option<Vec<Point>> {
+    let (dx, dy) = (b.x - a.x, b.y - a.y);
+    if dx != 0 && dy != 0 && dx.abs() != dy.abs() {
+        return None;
+    }
+    let step = Point::new(dx.signum(), dy.signum());
+    let len = dx.abs().max(dy.abs());
+    Some((0..=len).map(|i| Point::new(a.x + step.x * i, a.y + step.y * i)).collect())
+}
+
+/// Split a grid into connected regions (4-connectivity), where two adjacent
+/// cells belong together if `connects(a, b)` holds (e.g. `|a, b| a == b`).
+/// Regions are ordered by their first cell in row-major order, and each
+/// region's points are in reading order.
+pub fn regions<T, F>(grid: &[Vec<T>], connects: F) -> Vec<Vec<Point>>
+where
+    F: Fn(&T, &T) -> bool,
+{
+    let mut seen: HashSet<Point> = HashSet::new();
+    let mut out = Vec::new();
+
+    for (y, row) in grid.iter().enumerate() {
+        for x in 0..row.len() {
+            let start = Point::new(x as i32, y as i32);
+            if seen.contains(&start) {
+                continue;
+            }
+            let mut region: Vec<Point> = bfs(start, |p: Point| {
+                let here = cell_at(grid, p).unwrap();
+                p.neighbors4()
+                    .into_iter()
+                    .filter(|&nb| cell_at(grid, nb).is_some_and(|there| connects(here, there)))
+                    .collect::<Vec<_>>()
+            })
+            .into_keys()
+            .collect();
+            region.sort_by_key(|p| p.reading_order_key());
+            seen.extend(region.iter().copied());
+            out.push(region);
+        }
+    }
+
+    out
+}
+
+/// The n-th point (0-based) of the outward square spiral used by `spiral`:
+/// (0, 0), then right 1, up 1, left 2, down 2, right 3, ... with y pointing down.
+pub fn spiral_index(n: usize) -> Point {
+    if n == 0 {
+        return Point::new(0, 0);
+    }
+    let m = n as i64 + 1; // 1-based value on the Ulam spiral
+    let k = ((m - 1).isqrt() + 1) / 2; // ring, ending at (2k + 1)^2 in (k, k)
+    let t = m - (2 * k - 1) * (2 * k - 1); // 1..=8k along the ring
+    let side = 2 * k;
+    let (x, y) = match (t - 1) / side {
+        0 => (k, k - t),                     // up the right edge
+        1 => (k - (t - side), -k),           // left along the top
+        2 => (-k, -k + (t - 2 * side)),      // down the left edge
+        _ => (-k + (t - 3 * side), k),       // right along the bottom
+    };
+    Point::new(x as i32, y as i32)
+}
+
+/// Endless outward square spiral starting at the origin; see `spiral_index`.
+pub fn spiral() -> impl Iterator<Item = Point> {
+    (0..).map(spiral_index)
+}
+
+/// One step of a cellular automaton on an unbounded grid.
+///
+/// `rule(is_alive, live_neighbors)` decides whether a cell is alive next step,
+/// counting the 8 surrounding cells. Only live cells and their neighbors are
+/// considered, so a dead cell with no live neighbors always stays dead.
+pub fn life_step<F>(live: &HashSet<Point>, rule: F) -> HashSet<Point>
+where
+    F: Fn(bool, usize) -> bool,
+{
+    let mut counts: HashMap<Point, usize> = HashMap::new();
+    for p in live {
+        counts.entry(*p).or_insert(0);
+        for nb in p.neighbors8() {
+            *counts.entry(nb).or_insert(0) += 1;
+        }
+    }
+
+    counts
+        .into_iter()
+        .filter(|&(p, n)| rule(live.contains(&p), n))
+        .map(|(p, _)| p)
+        .collect()
+}
+
+/// Surround a grid with `border` rows and columns of `fill` on every side.
+pub fn pad<T: Clone>(grid: &[Vec<T>], border: usize, fill: T) -> Vec<Vec<T>> {
+    let width = grid.first().map_or(0, |row| row.len()) + 2 * border;
+    let blank = vec![fill.clone(); width];
+
+    let mut out = vec![blank.clone(); border];
+    for row in grid {
+        let mut padded = Vec::with_capacity(width);
+        padded.extend(std::iter::repeat_n(fill.clone(), border));
+        padded.extend(row.iter().cloned());
+        padded.extend(std::iter::repeat_n(fill.clone(), border));
+        out.push(padded);
+    }
+    out.extend(std::iter::repeat_n(blank, border));
+    out
+}
+
+/// Remove `top`/`bottom` rows and `left`/`right` columns; the inverse of `pad`.
+/// Cropping away more than the grid has leaves empty rows or no rows at all.
+pub fn crop<T: Clone>(
+    grid: &[Vec<T>],
+    top: usize,
+    bottom: usize,
+    left: usize,
+    right: usize,
+) -> Vec<Vec<T>> {
+    let rows = grid.len().saturating_sub(top + bottom);
+    grid.iter()
+        .skip(top)
+        .take(rows)
+        .map(|row| {
+            let cols = row.len().saturating_sub(left + right);
+            row.iter().skip(left).take(cols).cloned().collect()
+        })
+        .collect()
+}
+
+/// Copy of the `width` x `height` window whose top-left cell is `top_left`,
+/// or None if any part of the window falls outside the grid.
+pub fn subgrid<T: Clone>(
+    grid: &[Vec<T>],
+    top_left: Point,
+    width: usize,
+    height: usize,
+) -> Option<Vec<Vec<T>>> {
+    let x = usize::try_from(top_left.x).ok()?;
+    let y = usize::try_from(top_left.y).ok()?;
+    grid.get(y..y + height)?
+        .iter()
+        .map(|row| row.get(x..x + width).map(|cells| cells.to_vec()))
+        .collect()
+}
+
+/// Shortest number of steps from `start` to `goal` over passable cells, where
+/// standing on a key of `portals` also lets you step to its paired cell for
+/// one step. Include both directions in `portals` for two-way portals.
+pub fn bfs_with_portals(
+    grid: &[Vec<char>],
+    start: Point,
+    goal: Point,
+    portals: &HashMap<Point, Point>,
+    passable: impl Fn(char) -> bool,
+) -> Option<usize> {
+    let dist = bfs(start, |p: Point| {
+        p.neighbors4()
+            .into_iter()
+            .filter(|&nb| cell_at(grid, nb).is_some_and(|&c| passable(c)))
+            .chain(portals.get(&p).copied())
+            .collect::<Vec<_>>()
+    });
+    dist.get(&goal).copied()
+}
+
+/// Like `bfs_with_portals`, but for recursive mazes: the search state is
+/// (point, depth). Taking an `inner` portal leads one level deeper, taking an
+/// `outer` portal one level up; outer portals are closed at depth 0, and the
+/// goal only counts at depth 0. Levels deeper than `max_depth` are not
+/// explored, which keeps the search finite when the goal is unreachable.
+pub fn bfs_recursive_portals(
+    grid: &[Vec<char>],
+    start: Point,
+    goal: Point,
+    inner: &HashMap<Point, Point>,
+    outer: &HashMap<Point, Point>,
+    max_depth: usize,
+    passable: impl Fn(char) -> bool,
+) -> Option<usize> {
+    let dist = bfs((start, 0_usize), |(p, depth): (Point, usize)| {
+        let walk = p
+            .neighbors4()
+            .into_iter()
+            .filter(|&nb| cell_at(grid, nb).is_some_and(|&c| passable(c)))
+            .map(move |nb| (nb, depth));
+        let down = inner.get(&p).filter(|_| depth < max_depth).map(|&q| (q, depth + 1));
+        let up = outer.get(&p).filter(|_| depth > 0).map(|&q| (q, depth - 1));
+        walk.chain(down).chain(up).collect::<Vec<_>>()
+    });
+    dist.get(&(goal, 0)).copied()
+}
+
+/// Cheapest cost from `start` to every cell with 4-connectivity, where entering
+/// a cell costs its value (the start itself costs 0). Returned in the same
+/// shape as the grid; cells that can't be reached are None. Panics if a
+/// cheapest cost exceeds u32::MAX.
+pub fn weighted_flood(grid: &[Vec<u32>], start: Point) -> Vec<Vec<Option<u32>>> {
+    let (dist, _) = dijkstra(start, |p: Point| {
+        p.neighbors4()
+            .into_iter()
+            .filter_map(|nb| cell_at(grid, nb).map(|&c| (nb, c as i64)))
+            .collect::<Vec<_>>()
+    });
+
+    grid.iter()
+        .enumerate()
+        .map(|(y, row)| {
+            (0..row.len())
+                .map(|x| dist.get(&Point::new(x as i32, y as i32)).map(|&d| u32::try_from(d).expect("weighted_flood cost overflows u32")))
+                .collect()
+        })
+        .collect()
+}
+
+/// Sum of Manhattan distances over all unordered pairs of `points`, in
+/// O(n log n): each axis is sorted and contributes independently.
+pub fn sum_pairwise_manhattan(points: &[Point]) -> i64 {
+    fn axis_sum(mut v: Vec<i64>) -> i64 {
+        v.sort_unstable();
+        // the i-th smallest value is subtracted from the i values before it
+        v.iter()
+            .enumerate()
+            .fold((0, 0), |(total, prefix), (i, &x)| (total + x * i as i64 - prefix, prefix + x))
+            .0
+    }
+    axis_sum(points.iter().map(|p| p.x as i64).collect())
+        + axis_sum(points.iter().map(|p| p.y as i64).collect())
+}
+
+/// Crossing point of segments `a1..=a2` and `b1..=b2` (endpoints included),
+/// if they meet in exactly one point and that point has integer coordinates.
+/// Parallel and collinear (even overlapping) segments give None.
+pub fn segment_intersection(a1: Point, a2: Point, b1: Point, b2: Point) -> Option<Point> {
+    let cross = |ax: i64, ay: i64, bx: i64, by: i64| ax * by - ay * bx;
+    let (rx, ry) = ((a2.x - a1.x) as i64, (a2.y - a1.y) as i64);
+    let (sx, sy) = ((b2.x - b1.x) as i64, (b2.y - b1.y) as i64);
+    let (qx, qy) = ((b1.x - a1.x) as i64, (b1.y - a1.y) as i64);
+
+    let mut d = cross(rx, ry, sx, sy);
+    if d == 0 {
+        return None;
+    }
+    // a1 + r * t/d == b1 + s * u/d, with both fractions in [0, 1]
+    let (mut t, mut u) = (cross(qx, qy, sx, sy), cross(qx, qy, rx, ry));
+    if d < 0 {
+        (d, t, u) = (-d, -t, -u);
+    }
+    if !(0..=d).contains(&t) || !(0..=d).contains(&u) {
+        return None;
+    }
+    let (x, y) = (rx * t, ry * t);
+    if x % d != 0 || y % d != 0 {
+        return None;
+    }
+    Some(Point::new(a1.x + (x / d) as i32, a1.y + (y / d) as i32))
+}
+
+/// Cheapest path from `start` to `goal` moving in 4 directions, where entering
+/// a cell costs `cost(&value)` (the start cell itself is free). Returns the
+/// total cost and the path start..=goal, or None if unreachable.
+pub fn grid_dijkstra<F>(grid: &[Vec<u8>], start: Point, goal: Point, cost: F) -> Option<(i64, Vec<Point>)>
+where
+    F: Fn(&u8) -> i64,
+{
+    dijkstra_to(start, goal, |p: Point| {
+        p.neighbors4()
+            .into_iter()
+            .filter_map(|nb| cell_at(grid, nb).map(|c| (nb, cost(c))))
+            .collect::<Vec<_>>()
+    })
 }
 
 #[cfg(test)]
@@ -125,6 +1055,24 @@ mod tests {
         assert_eq!(a.manhattan(b), 7);
     }
 
+    #[test]
+    fn chebyshev_and_dist_sq_basic() {
+        let a = Point::new(0, 0);
+        let b = Point::new(3, 4);
+        assert_eq!(a.chebyshev(b), 4);
+        assert_eq!(a.dist_sq(b), 25);
+        assert_eq!(b.chebyshev(a), 4);
+        assert_eq!(Point::new(-2, 5).chebyshev(Point::new(1, 4)), 3);
+    }
+
+    #[test]
+    fn dist_sq_does_not_overflow_i32() {
+        let a = Point::new(0, 0);
+        let b = Point::new(i32::MAX, i32::MAX);
+        let d = i32::MAX as i64;
+        assert_eq!(a.dist_sq(b), 2 * d * d);
+    }
+
     #[test]
     fn neighbors4_are_correct() {
         let p = Point::new(10, 20);
@@ -167,20 +1115,131 @@ mod tests {
     }
 
     #[test]
-    fn debug_format_is_expected() {
-        let p = Point::new(3, 4);
-        assert_eq!(format!("{:?}", p), "(3, 4)");
+    fn reading_order_key_sorts_rows_first() {
+        let mut pts = vec![
+            Point::new(2, 1),
+            Point::new(0, 2),
+            Point::new(5, 0),
+            Point::new(1, 1),
+        ];
+
+        pts.sort_by_key(|p| p.reading_order_key());
+        assert_eq!(
+            pts,
+            vec![Point::new(5, 0), Point::new(1, 1), Point::new(2, 1), Point::new(0, 2)]
+        );
+
+        // derived Ord sorts by x first, which differs
+        pts.sort();
+        assert_eq!(pts[0], Point::new(0, 2));
     }
 
     #[test]
-    fn point_is_hashable_and_uses_both_coords() {
-        let mut set = HashSet::new();
-        set.insert(Point::new(1, 2));
-        assert!(set.contains(&Point::new(1, 2)));
-        assert!(!set.contains(&Point::new(2, 1)));
-    }
+    fn manhattan_disk_sizes() {
+        let c = Point::new(3, -1);
+        let disk: HashSet<Point> = c.manhattan_disk(1).collect();
+        assert_eq!(disk.len(), 5);
+        assert!(disk.contains(&c));
+        assert!(c.neighbors4().iter().all(|n| disk.contains(n)));
 
-    // -------- GridExt tests --------
+        assert_eq!(c.manhattan_disk(2).count(), 13);
+        assert!(c.manhattan_disk(3).all(|p| p.manhattan(c) <= 3));
+    }
+
+    #[test]
+    fn manhattan_ring_sizes() {
+        let c = Point::new(0, 0);
+        let ring: HashSet<Point> = c.manhattan_ring(2).collect();
+        assert_eq!(ring.len(), 8);
+        assert!(ring.iter().all(|p| p.manhattan(c) == 2));
+
+        assert_eq!(c.manhattan_ring(0).collect::<Vec<_>>(), vec![c]);
+        assert_eq!(c.manhattan_ring(5).collect::<HashSet<_>>().len(), 20);
+    }
+
+    #[test]
+    fn scale_multiplies_both_components() {
+        assert_eq!(Point::new(2, -3).scale(4), Point::new(8, -12));
+        assert_eq!(Point::new(2, -3).scale(0), Point::new(0, 0));
+    }
+
+    #[test]
+    fn rotate90_cw_and_ccw_directions() {
+        let up = Point::new(0, -1);
+        let right = Point::new(1, 0);
+        assert_eq!(up.rotate90_cw(), right);
+        assert_eq!(right.rotate90_ccw(), up);
+        assert_eq!(Point::new(3, 1).rotate90_cw(), Point::new(-1, 3));
+    }
+
+    #[test]
+    fn four_rotations_return_original_point() {
+        let p = Point::new(5, -2);
+        assert_eq!(p.rotate90_cw().rotate90_cw().rotate90_cw().rotate90_cw(), p);
+        assert_eq!(p.rotate90_ccw().rotate90_ccw().rotate90_ccw().rotate90_ccw(), p);
+        assert_eq!(p.rotate90_cw().rotate90_ccw(), p);
+    }
+
+    #[test]
+    fn debug_format_is_expected() {
+        let p = Point::new(3, 4);
+        assert_eq!(format!("{:?}", p), "(3, 4)");
+    }
+
+    #[test]
+    fn point_is_hashable_and_uses_both_coords() {
+        let mut set = HashSet::new();
+        set.insert(Point::new(1, 2));
+        assert!(set.contains(&Point::new(1, 2)));
+        assert!(!set.contains(&Point::new(2, 1)));
+    }
+
+    // -------- Point3 tests --------
+
+    #[test]
+    fn point3_manhattan_distance() {
+        let a = Point3::new(1, -2, 3);
+        let b = Point3::new(-1, 2, 0);
+        // 2 + 4 + 3
+        assert_eq!(a.manhattan(b), 9);
+        assert_eq!(b.manhattan(a), 9);
+    }
+
+    #[test]
+    fn point3_neighbors6_are_correct() {
+        let p = Point3::new(1, 2, 3);
+        assert_eq!(
+            p.neighbors6(),
+            [
+                Point3::new(2, 2, 3),
+                Point3::new(0, 2, 3),
+                Point3::new(1, 3, 3),
+                Point3::new(1, 1, 3),
+                Point3::new(1, 2, 4),
+                Point3::new(1, 2, 2),
+            ]
+        );
+    }
+
+    #[test]
+    fn point3_neighbors26_are_distinct_and_adjacent() {
+        let p = Point3::new(0, 0, 0);
+        let ns: HashSet<Point3> = p.neighbors26().into_iter().collect();
+        assert_eq!(ns.len(), 26);
+        assert!(!ns.contains(&p));
+        assert!(ns.iter().all(|n| n.x.abs() <= 1 && n.y.abs() <= 1 && n.z.abs() <= 1));
+    }
+
+    #[test]
+    fn point3_add_sub_and_debug() {
+        let a = Point3::new(1, 2, 3);
+        let b = Point3::new(4, 5, 6);
+        assert_eq!(a + b, Point3::new(5, 7, 9));
+        assert_eq!(b - a, Point3::new(3, 3, 3));
+        assert_eq!(format!("{:?}", a), "(1, 2, 3)");
+    }
+
+    // -------- GridExt tests --------
 
     #[test]
     fn width_and_height_on_non_empty_grid() {
@@ -240,4 +1299,827 @@ mod tests {
         // out of bounds still returns None
         assert!(grid.get_point_mut(Point::new(2, 0)).is_none());
     }
+
+    #[test]
+    fn neighbors4_wrapped_on_3x3() {
+        let grid = vec![vec![0; 3]; 3];
+        let ns = grid.neighbors4_wrapped(Point::new(0, 0));
+
+        // same order as neighbors4: right, left, down, up
+        assert_eq!(ns, [Point::new(1, 0), Point::new(2, 0), Point::new(0, 1), Point::new(0, 2)]);
+        assert!(ns.iter().all(|&n| grid.in_bounds(n)));
+    }
+
+    #[test]
+    #[should_panic(expected = "empty grid")]
+    fn neighbors4_wrapped_on_empty_grid_panics() {
+        let grid: Vec<Vec<u8>> = vec![];
+        grid.neighbors4_wrapped(Point::new(0, 0));
+    }
+
+    #[test]
+    fn get_point_wrapped_never_misses_on_non_empty_grid() {
+        let grid = vec![vec![1, 2, 3], vec![4, 5, 6]];
+        assert_eq!(grid.get_point_wrapped(Point::new(-1, 0)), Some(&3));
+        assert_eq!(grid.get_point_wrapped(Point::new(3, -1)), Some(&4));
+        assert_eq!(grid.get_point_wrapped(Point::new(7, 5)), Some(&5));
+
+        let empty: Vec<Vec<i32>> = Vec::new();
+        assert_eq!(empty.get_point_wrapped(Point::new(0, 0)), None);
+    }
+
+    #[test]
+    fn get_rc_matches_get_point_with_flipped_coords() {
+        let grid = vec![
+            vec![10, 11, 12],
+            vec![20, 21, 22],
+        ];
+
+        assert_eq!(grid.get_rc(1, 2), grid.get_point(Point::new(2, 1)));
+        assert_eq!(grid.get_rc(1, 2), Some(&22));
+        assert_eq!(grid.get_rc(2, 1), None); // row out of bounds
+    }
+
+    #[test]
+    fn get_rc_mut_allows_mutation() {
+        let mut grid = vec![vec!['a', 'b'], vec!['c', 'd']];
+        *grid.get_rc_mut(1, 0).expect("in bounds") = 'X';
+        assert_eq!(grid[1][0], 'X');
+        assert!(grid.get_rc_mut(0, 2).is_none());
+    }
+
+    #[test]
+    fn iter_points_mut_updates_cells_by_position() {
+        let mut grid = vec![vec![1, 2, 3], vec![4, 5, 6]];
+
+        for (p, cell) in grid.iter_points_mut() {
+            // double the value, then add the x coordinate
+            *cell = *cell * 2 + p.x;
+        }
+
+        assert_eq!(grid, vec![vec![2, 5, 8], vec![8, 11, 14]]);
+    }
+
+    #[test]
+    fn iter_points_mut_yields_row_major_points() {
+        let mut grid = vec![vec!['a', 'b'], vec!['c', 'd']];
+        let points: Vec<Point> = grid.iter_points_mut().map(|(p, _)| p).collect();
+        assert_eq!(
+            points,
+            vec![Point::new(0, 0), Point::new(1, 0), Point::new(0, 1), Point::new(1, 1)]
+        );
+    }
+
+    #[test]
+    fn corners_and_edge_points_on_3x3_grid() {
+        let grid = vec![vec![0; 3]; 3];
+
+        assert_eq!(
+            grid.corners(),
+            [Point::new(0, 0), Point::new(2, 0), Point::new(0, 2), Point::new(2, 2)]
+        );
+
+        let edges = grid.edge_points();
+        assert_eq!(edges.len(), 8);
+        assert!(!edges.contains(&Point::new(1, 1)));
+    }
+
+    #[test]
+    fn edge_points_on_degenerate_grids() {
+        let row = vec![vec![0; 4]]; // 1 row, 4 cols
+        assert_eq!(row.edge_points().len(), 4);
+        assert_eq!(row.corners()[0], row.corners()[2]);
+
+        let col = vec![vec![0]; 3]; // 3 rows, 1 col
+        assert_eq!(
+            col.edge_points(),
+            vec![Point::new(0, 0), Point::new(0, 1), Point::new(0, 2)]
+        );
+
+        let empty: Vec<Vec<i32>> = Vec::new();
+        assert!(empty.edge_points().is_empty());
+    }
+
+    // -------- grid_bfs tests --------
+
+    #[test]
+    fn grid_bfs_respects_walls() {
+        let grid: Vec<Vec<char>> = ["S.#.", "#.#.", "...."]
+            .iter()
+            .map(|l| l.chars().collect())
+            .collect();
+
+        let dist = grid_bfs(&grid, Point::new(0, 0), |&c| c != '#');
+
+        assert_eq!(dist.get(&Point::new(0, 0)), Some(&0));
+        assert_eq!(dist.get(&Point::new(1, 0)), Some(&1));
+        assert_eq!(dist.get(&Point::new(1, 2)), Some(&3));
+        assert_eq!(dist.get(&Point::new(3, 0)), Some(&7)); // around the wall
+        assert_eq!(dist.get(&Point::new(2, 0)), None); // wall
+        assert_eq!(dist.len(), 9); // every open cell
+    }
+
+    #[test]
+    fn grid_bfs_stays_in_bounds() {
+        let grid = vec![vec!['.']];
+        let dist = grid_bfs(&grid, Point::new(0, 0), |_| true);
+        assert_eq!(dist.len(), 1);
+    }
+
+    // -------- NearestIndex tests --------
+
+    #[test]
+    fn nearest_index_returns_closest_point() {
+        let index = NearestIndex::new([
+            Point::new(1, 1),
+            Point::new(1, 6),
+            Point::new(8, 3),
+            Point::new(3, 4),
+            Point::new(5, 5),
+            Point::new(8, 9),
+        ])
+        .expect("non-empty");
+
+        assert_eq!(index.nearest(Point::new(0, 0)), (Point::new(1, 1), 2));
+        assert_eq!(index.nearest(Point::new(5, 4)), (Point::new(5, 5), 1));
+        assert_eq!(index.nearest(Point::new(8, 9)), (Point::new(8, 9), 0));
+    }
+
+    #[test]
+    fn nearest_index_ties_and_empty() {
+        let index = NearestIndex::new([Point::new(2, 0), Point::new(0, 0)]).expect("non-empty");
+        // (1, 0) is 1 away from both
+        assert_eq!(index.nearest(Point::new(1, 0)), (Point::new(0, 0), 1));
+
+        assert!(NearestIndex::new(Vec::new()).is_none());
+    }
+
+    // -------- rotate tests --------
+
+    #[test]
+    fn rotate_quarter_turn_clockwise() {
+        // 1 2 3      4 1
+        // 4 5 6  ->  5 2
+        //            6 3
+        let grid = vec![vec![1, 2, 3], vec![4, 5, 6]];
+        assert_eq!(rotate(&grid, 1), vec![vec![4, 1], vec![5, 2], vec![6, 3]]);
+        assert_eq!(rotate(&grid, 3), vec![vec![3, 6], vec![2, 5], vec![1, 4]]);
+    }
+
+    #[test]
+    fn rotate_composes_and_wraps() {
+        let grid = vec![vec!['a', 'b', 'c'], vec!['d', 'e', 'f']];
+        assert_eq!(rotate(&grid, 4), grid);
+        assert_eq!(rotate(&grid, 2), rotate(&rotate(&grid, 1), 1));
+        assert_eq!(rotate(&grid, 3), rotate(&rotate(&grid, 2), 1));
+        assert_eq!(rotate(&grid, 7), rotate(&grid, 3));
+    }
+
+    // -------- grid_points tests --------
+
+    #[test]
+    fn grid_points_is_row_major() {
+        let pts: Vec<Point> = grid_points(2, 2).collect();
+        assert_eq!(
+            pts,
+            vec![Point::new(0, 0), Point::new(1, 0), Point::new(0, 1), Point::new(1, 1)]
+        );
+        assert_eq!(grid_points(0, 5).count(), 0);
+    }
+
+    #[test]
+    fn points_covers_whole_grid() {
+        let grid = vec![vec!['a', 'b', 'c'], vec!['d', 'e', 'f']];
+        let cells: String = grid.points().map(|p| grid.get_point(p).unwrap()).collect();
+        assert_eq!(cells, "abcdef");
+    }
+
+    // -------- grid_to_edges tests --------
+
+    #[test]
+    fn grid_to_edges_counts_passable_adjacencies() {
+        // ..#
+        // ...
+        let grid: Vec<Vec<char>> = ["..#", "..."].iter().map(|l| l.chars().collect()).collect();
+        let edges = grid_to_edges(&grid, |c| c == '.');
+
+        // row 0: (0,0)-(1,0); row 1: (0,1)-(1,1), (1,1)-(2,1); vertical: x=0, x=1
+        assert_eq!(edges.len(), 5);
+        assert!(edges.contains(&(Point::new(1, 0), Point::new(1, 1))));
+        assert!(!edges.iter().any(|&(a, b)| a == Point::new(2, 0) || b == Point::new(2, 0)));
+    }
+
+    #[test]
+    fn grid_to_edges_feeds_mst() {
+        let grid: Vec<Vec<char>> = ["...", "..."].iter().map(|l| l.chars().collect()).collect();
+        let edges: Vec<(Point, Point, i64)> = grid_to_edges(&grid, |c| c == '.')
+            .into_iter()
+            .map(|(a, b)| (a, b, 1))
+            .collect();
+
+        let (total, chosen) = crate::search::mst(&edges);
+        assert_eq!(chosen.len(), 5); // 6 cells, one component
+        assert_eq!(total, 5);
+    }
+
+    // -------- longest_grid_path tests --------
+
+    const LONG_WALK: &str = "\
+#.#####################
+#.......#########...###
+#######.#########.#.###
+###.....#.>.>.###.#.###
+###v#####.#v#.###.#.###
+###.>...#.#.#.....#...#
+###v###.#.#.#########.#
+###...#.#.#.......#...#
+#####.#.#.#######.#.###
+#.....#.#.#.......#...#
+#.#####.#.#.#########v#
+#.#...#...#...###...>.#
+#.#.#v#######v###.###v#
+#...#.>.#...>.>.#.###.#
+#####v#.#.###v#.#.###.#
+#.....#...#...#.#.#...#
+#.#########.###.#.#.###
+#...###...#...#...#.###
+###.###.#.###v#####v###
+#...#...#.#.>.#...>.###
+#.###.###.#.###.#.#v###
+#.....###...###...#...#
+#####################.#
+";
+
+    #[test]
+    fn longest_grid_path_on_example() {
+        let grid: Vec<Vec<char>> = LONG_WALK.lines().map(|l| l.chars().collect()).collect();
+        let start = Point::new(1, 0);
+        let goal = Point::new(21, 22);
+
+        assert_eq!(longest_grid_path(&grid, start, goal, true), Some(94));
+        assert_eq!(longest_grid_path(&grid, start, goal, false), Some(154));
+    }
+
+    #[test]
+    fn longest_grid_path_keeps_parallel_corridors() {
+        let grid: Vec<Vec<char>> = "\
+#.#########
+#.........#
+#.#######.#
+#.#######.#
+#.........#
+#.#########
+#.#########"
+            .lines()
+            .map(|l| l.chars().collect())
+            .collect();
+        // short corridor straight down (3) vs the loop round the right (19)
+        assert_eq!(longest_grid_path(&grid, Point::new(1, 0), Point::new(1, 6), false), Some(22));
+    }
+
+    #[test]
+    fn longest_grid_path_rejects_closed_or_outside_endpoints() {
+        let grid: Vec<Vec<char>> = vec!["#.#".chars().collect(), "#.#".chars().collect()];
+        assert_eq!(longest_grid_path(&grid, Point::new(0, 0), Point::new(1, 1), false), None);
+        assert_eq!(longest_grid_path(&grid, Point::new(1, 0), Point::new(1, 5), false), None);
+        assert_eq!(longest_grid_path(&grid, Point::new(1, 0), Point::new(1, 1), false), Some(1));
+    }
+
+    #[test]
+    fn longest_grid_path_unreachable_goal() {
+        let grid: Vec<Vec<char>> = [".#.", ".#."].iter().map(|l| l.chars().collect()).collect();
+        assert_eq!(longest_grid_path(&grid, Point::new(0, 0), Point::new(2, 1), false), None);
+    }
+
+    // -------- line tests --------
+
+    #[test]
+    fn line_diagonal_and_horizontal() {
+        assert_eq!(
+            line(Point::new(0, 0), Point::new(2, 2)),
+            vec![Point::new(0, 0), Point::new(1, 1), Point::new(2, 2)]
+        );
+        assert_eq!(
+            line(Point::new(3, 5), Point::new(0, 5)),
+            vec![Point::new(3, 5), Point::new(2, 5), Point::new(1, 5), Point::new(0, 5)]
+        );
+        assert_eq!(line(Point::new(4, 4), Point::new(4, 4)), vec![Point::new(4, 4)]);
+    }
+
+    #[test]
+    fn line_shallow_slope_is_connected() {
+        let pts = line(Point::new(0, 0), Point::new(6, 2));
+        assert_eq!(pts.len(), 7); // one point per column
+        assert_eq!(pts.first(), Some(&Point::new(0, 0)));
+        assert_eq!(pts.last(), Some(&Point::new(6, 2)));
+        assert!(pts.windows(2).all(|w| w[0].chebyshev(w[1]) == 1));
+    }
+
+    #[test]
+    fn line_orthogonal_or_diagonal_cases() {
+        assert_eq!(
+            line_orthogonal_or_diagonal(Point::new(1, 1), Point::new(1, 3)),
+            Some(vec![Point::new(1, 1), Point::new(1, 2), Point::new(1, 3)])
+        );
+        assert_eq!(
+            line_orthogonal_or_diagonal(Point::new(9, 7), Point::new(7, 9)),
+            Some(vec![Point::new(9, 7), Point::new(8, 8), Point::new(7, 9)])
+        );
+        assert_eq!(line_orthogonal_or_diagonal(Point::new(0, 0), Point::new(2, 1)), None);
+    }
+
+    // -------- grid_from_rows tests --------
+
+    #[test]
+    fn grid_from_rows_accepts_rectangular_input() {
+        let rows = vec![vec![1, 2], vec![3, 4], vec![5, 6]];
+        assert_eq!(grid_from_rows(rows.clone()), Ok(rows));
+        assert_eq!(grid_from_rows(Vec::<Vec<u8>>::new()), Ok(vec![]));
+    }
+
+    #[test]
+    fn grid_from_rows_reports_ragged_row() {
+        let rows = vec![vec!['a', 'b', 'c'], vec!['d', 'e', 'f'], vec!['g']];
+        let err = grid_from_rows(rows).unwrap_err();
+
+        assert_eq!(err, GridError::Ragged { row: 2, len: 1, expected: 3 });
+        assert_eq!(format!("{err}"), "ragged grid: row 2 has length 1, expected 3");
+    }
+
+    // -------- surface_area tests --------
+
+    fn boulder_example() -> HashSet<Point3> {
+        [
+            (2, 2, 2), (1, 2, 2), (3, 2, 2), (2, 1, 2), (2, 3, 2), (2, 2, 1), (2, 2, 3),
+            (2, 2, 4), (2, 2, 6), (1, 2, 5), (3, 2, 5), (2, 1, 5), (2, 3, 5),
+        ]
+        .into_iter()
+        .map(|(x, y, z)| Point3::new(x, y, z))
+        .collect()
+    }
+
+    #[test]
+    fn surface_area_matches_example() {
+        assert_eq!(surface_area(&boulder_example()), 64);
+
+        let pair: HashSet<Point3> = [Point3::new(1, 1, 1), Point3::new(2, 1, 1)].into();
+        assert_eq!(surface_area(&pair), 10);
+    }
+
+    #[test]
+    fn exterior_surface_area_excludes_air_pockets() {
+        assert_eq!(exterior_surface_area(&boulder_example()), 58);
+        assert_eq!(exterior_surface_area(&HashSet::new()), 0);
+    }
+
+    // -------- bounding_box_3d / flood_exterior tests --------
+
+    #[test]
+    fn bounding_box_3d_corners() {
+        let pts = [Point3::new(1, -2, 3), Point3::new(-1, 4, 0), Point3::new(2, 0, 5)];
+        assert_eq!(
+            bounding_box_3d(pts),
+            Some((Point3::new(-1, -2, 0), Point3::new(2, 4, 5)))
+        );
+        assert_eq!(bounding_box_3d(Vec::new()), None);
+    }
+
+    #[test]
+    fn flood_exterior_skips_hollow_center() {
+        // 3x3x3 cube shell with the center removed
+        let shell: HashSet<Point3> = (0..3)
+            .flat_map(|x| (0..3).flat_map(move |y| (0..3).map(move |z| Point3::new(x, y, z))))
+            .filter(|&p| p != Point3::new(1, 1, 1))
+            .collect();
+
+        let bounds = bounding_box_3d(shell.iter().copied()).unwrap();
+        let outside = flood_exterior(&shell, bounds);
+
+        assert!(!outside.contains(&Point3::new(1, 1, 1)));
+        assert!(outside.contains(&Point3::new(-1, -1, -1)));
+        assert_eq!(outside.len(), 5 * 5 * 5 - 26 - 1); // minus shell and center
+
+        assert_eq!(surface_area(&shell), 54 + 6);
+        assert_eq!(exterior_surface_area(&shell), 54);
+    }
+
+    // -------- regions tests --------
+
+    #[test]
+    fn regions_splits_letter_blocks() {
+        let grid: Vec<Vec<char>> = ["AAB", "ABB", "CCB"].iter().map(|l| l.chars().collect()).collect();
+        let regs = regions(&grid, |a, b| a == b);
+
+        let sizes: Vec<usize> = regs.iter().map(|r| r.len()).collect();
+        assert_eq!(sizes, vec![3, 4, 2]); // A, B, C in order of first cell
+        assert_eq!(regs[0], vec![Point::new(0, 0), Point::new(1, 0), Point::new(0, 1)]);
+    }
+
+    #[test]
+    fn regions_same_value_not_adjacent_are_separate() {
+        let grid = vec![vec![1, 0, 1]];
+        let regs = regions(&grid, |a, b| a == b);
+        assert_eq!(regs.len(), 3);
+    }
+
+    // -------- spiral tests --------
+
+    #[test]
+    fn spiral_first_points_match_known_order() {
+        let pts: Vec<(i32, i32)> = spiral().take(11).map(|p| (p.x, p.y)).collect();
+        assert_eq!(
+            pts,
+            vec![
+                (0, 0),
+                (1, 0),
+                (1, -1),
+                (0, -1),
+                (-1, -1),
+                (-1, 0),
+                (-1, 1),
+                (0, 1),
+                (1, 1),
+                (2, 1),
+                (2, 0),
+            ]
+        );
+    }
+
+    #[test]
+    fn spiral_index_matches_walk() {
+        // walking: run lengths 1, 1, 2, 2, 3, 3, ... in directions R, U, L, D
+        let dirs = [Point::new(1, 0), Point::new(0, -1), Point::new(-1, 0), Point::new(0, 1)];
+        let mut p = Point::new(0, 0);
+        let mut n = 0;
+        for run in 0..40 {
+            let d = dirs[run % 4];
+            for _ in 0..(run / 2 + 1) {
+                assert_eq!(spiral_index(n), p, "index {n}");
+                p = Point::new(p.x + d.x, p.y + d.y);
+                n += 1;
+            }
+        }
+        // spiral memory: square 1024 (index 1023) is 31 steps from the origin
+        assert_eq!(spiral_index(1023).manhattan(Point::new(0, 0)), 31);
+    }
+
+    // -------- life_step tests --------
+
+    fn conway(alive: bool, n: usize) -> bool {
+        n == 3 || (alive && n == 2)
+    }
+
+    #[test]
+    fn life_step_blinker_has_period_two() {
+        let horizontal: HashSet<Point> = [Point::new(0, 1), Point::new(1, 1), Point::new(2, 1)].into();
+        let vertical: HashSet<Point> = [Point::new(1, 0), Point::new(1, 1), Point::new(1, 2)].into();
+
+        let once = life_step(&horizontal, conway);
+        assert_eq!(once, vertical);
+        assert_eq!(life_step(&once, conway), horizontal);
+    }
+
+    #[test]
+    fn life_step_block_is_still_and_lone_cell_dies() {
+        let block: HashSet<Point> =
+            [Point::new(0, 0), Point::new(1, 0), Point::new(0, 1), Point::new(1, 1)].into();
+        assert_eq!(life_step(&block, conway), block);
+
+        let lone: HashSet<Point> = [Point::new(5, 5)].into();
+        assert!(life_step(&lone, conway).is_empty());
+    }
+
+    // -------- pad / crop tests --------
+
+    #[test]
+    fn pad_2x2_by_one() {
+        let grid = vec![vec![1, 2], vec![3, 4]];
+        assert_eq!(
+            pad(&grid, 1, 0),
+            vec![
+                vec![0, 0, 0, 0],
+                vec![0, 1, 2, 0],
+                vec![0, 3, 4, 0],
+                vec![0, 0, 0, 0],
+            ]
+        );
+        assert_eq!(pad(&grid, 0, 9), grid);
+    }
+
+    #[test]
+    fn crop_undoes_pad() {
+        let grid = vec![vec!['a', 'b', 'c'], vec!['d', 'e', 'f']];
+        assert_eq!(crop(&pad(&grid, 2, '.'), 2, 2, 2, 2), grid);
+        assert_eq!(crop(&grid, 0, 1, 1, 0), vec![vec!['b', 'c']]);
+        assert!(crop(&grid, 1, 1, 0, 0).is_empty());
+    }
+
+    // -------- subgrid tests --------
+
+    #[test]
+    fn subgrid_extracts_window() {
+        let grid = vec![
+            vec![1, 2, 3, 4],
+            vec![5, 6, 7, 8],
+            vec![9, 10, 11, 12],
+        ];
+
+        assert_eq!(subgrid(&grid, Point::new(1, 1), 2, 2), Some(vec![vec![6, 7], vec![10, 11]]));
+        assert_eq!(subgrid(&grid, Point::new(0, 0), 4, 3), Some(grid.clone()));
+    }
+
+    #[test]
+    fn subgrid_out_of_bounds_is_none() {
+        let grid = vec![vec![1, 2, 3], vec![4, 5, 6]];
+
+        assert_eq!(subgrid(&grid, Point::new(2, 0), 2, 1), None); // too wide
+        assert_eq!(subgrid(&grid, Point::new(0, 1), 1, 2), None); // too tall
+        assert_eq!(subgrid(&grid, Point::new(-1, 0), 1, 1), None);
+    }
+
+    // -------- bfs_with_portals tests --------
+
+    #[test]
+    fn bfs_with_portals_uses_shortcut() {
+        // S and G are far apart on foot; portal a <-> b joins them
+        let grid: Vec<Vec<char>> = [
+            "S.a#.....",
+            "##.#.###.",
+            "...#.#b.G",
+            ".#####.##",
+            ".......##",
+        ]
+        .iter()
+        .map(|l| l.chars().collect())
+        .collect();
+        let start = Point::new(0, 0);
+        let goal = Point::new(8, 2);
+        let (a, b) = (Point::new(2, 0), Point::new(6, 2));
+
+        let none = HashMap::new();
+        let without = bfs_with_portals(&grid, start, goal, &none, |c| c != '#');
+        assert_eq!(without, Some(18)); // the long way round along the bottom
+
+        let portals = HashMap::from([(a, b), (b, a)]);
+        let with = bfs_with_portals(&grid, start, goal, &portals, |c| c != '#');
+        // S -> a (2), a -> b (1), b -> G (2)
+        assert_eq!(with, Some(5));
+    }
+
+    #[test]
+    fn bfs_with_portals_prefers_walking_when_shorter() {
+        let grid: Vec<Vec<char>> = ["....."].iter().map(|l| l.chars().collect()).collect();
+        let portals = HashMap::from([(Point::new(0, 0), Point::new(4, 0))]);
+        let d = bfs_with_portals(&grid, Point::new(1, 0), Point::new(2, 0), &portals, |_| true);
+        assert_eq!(d, Some(1));
+    }
+
+    // -------- bfs_recursive_portals tests --------
+
+    #[test]
+    fn bfs_recursive_portals_goes_down_and_back_up() {
+        // S . X # x . y # Y . G
+        // inner X -> x (down), outer y -> Y (up)
+        let grid: Vec<Vec<char>> = ["S.X#x.y#Y.G"].iter().map(|l| l.chars().collect()).collect();
+        let p = |x| Point::new(x, 0);
+        let inner = HashMap::from([(p(2), p(4)), (p(8), p(6))]);
+        let outer = HashMap::from([(p(4), p(2)), (p(6), p(8))]);
+
+        // S -> X (2), down (1), x -> y (2), up (1), Y -> G (2)
+        let d = bfs_recursive_portals(&grid, p(0), p(10), &inner, &outer, 10, |c| c != '#');
+        assert_eq!(d, Some(8));
+    }
+
+    #[test]
+    fn bfs_recursive_portals_goal_only_at_depth_zero() {
+        // both portals lead down, so G is only ever reached at depth 2
+        let grid: Vec<Vec<char>> = ["S.X#x.y#Y.G"].iter().map(|l| l.chars().collect()).collect();
+        let p = |x| Point::new(x, 0);
+        let inner = HashMap::from([(p(2), p(4)), (p(6), p(8))]);
+        let outer = HashMap::from([(p(4), p(2)), (p(8), p(6))]);
+
+        let d = bfs_recursive_portals(&grid, p(0), p(10), &inner, &outer, 10, |c| c != '#');
+        assert_eq!(d, None);
+        // flat portals would find it
+        let flat: HashMap<Point, Point> = inner.iter().chain(&outer).map(|(&a, &b)| (a, b)).collect();
+        assert_eq!(bfs_with_portals(&grid, p(0), p(10), &flat, |c| c != '#'), Some(8));
+    }
+
+    // -------- Rect tests --------
+
+    #[test]
+    fn rect_from_scattered_points() {
+        let pts = [Point::new(3, -1), Point::new(-2, 4), Point::new(0, 0), Point::new(1, 2)];
+        let r = Rect::from_points(&pts).expect("non-empty");
+
+        assert_eq!(r.min, Point::new(-2, -1));
+        assert_eq!(r.max, Point::new(3, 4));
+        assert_eq!((r.width(), r.height()), (6, 6));
+        assert!(pts.iter().all(|&p| r.contains(p)));
+        assert!(!r.contains(Point::new(4, 0)));
+
+        assert!(Rect::from_points(&[]).is_none());
+    }
+
+    #[test]
+    fn rect_iter_yields_every_point() {
+        let r = Rect { min: Point::new(1, 1), max: Point::new(3, 2) };
+        let pts: Vec<Point> = r.iter().collect();
+
+        assert_eq!(pts.len(), (r.width() * r.height()) as usize);
+        assert_eq!(pts.first(), Some(&r.min));
+        assert_eq!(pts.last(), Some(&r.max));
+        assert!(pts.iter().all(|&p| r.contains(p)));
+    }
+
+    // -------- weighted_flood tests --------
+
+    #[test]
+    fn weighted_flood_matches_manual_dijkstra() {
+        // the chiton example's top-left corner
+        let grid: Vec<Vec<u32>> = ["116", "138", "213"]
+            .iter()
+            .map(|l| l.chars().map(|c| c.to_digit(10).unwrap()).collect())
+            .collect();
+
+        let costs = weighted_flood(&grid, Point::new(0, 0));
+
+        assert_eq!(costs[0][0], Some(0));
+        assert_eq!(costs[0][2], Some(1 + 6));
+        // best to (2, 2): down 1, down 2, right 1, right 3 = 7
+        assert_eq!(costs[2][2], Some(7));
+        assert_eq!(
+            costs,
+            vec![
+                vec![Some(0), Some(1), Some(7)],
+                vec![Some(1), Some(4), Some(12)],
+                vec![Some(3), Some(4), Some(7)],
+            ]
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "overflows u32")]
+    fn weighted_flood_panics_instead_of_truncating() {
+        let grid = vec![vec![0, u32::MAX, u32::MAX]];
+        weighted_flood(&grid, Point::new(0, 0));
+    }
+
+    // -------- Direction tests --------
+
+    #[test]
+    fn direction_from_char_accepts_all_notations() {
+        for (cs, d) in [
+            ("UN^", Direction::North),
+            ("RE>", Direction::East),
+            ("DSv", Direction::South),
+            ("LW<", Direction::West),
+        ] {
+            for c in cs.chars() {
+                assert_eq!(Direction::from_char(c), Some(d));
+            }
+        }
+        assert_eq!(Direction::from_char('x'), None);
+    }
+
+    #[test]
+    fn direction_delta_has_y_down() {
+        assert_eq!(Direction::North.delta(), Point::new(0, -1));
+        assert_eq!(Direction::East.delta(), Point::new(1, 0));
+    }
+
+    #[test]
+    fn direction_turns_are_inverse() {
+        assert_eq!(Direction::North.turn_right(), Direction::East);
+        assert_eq!(Direction::North.turn_left(), Direction::West);
+        for d in [Direction::North, Direction::East, Direction::South, Direction::West] {
+            assert_eq!(d.turn_right().turn_left(), d);
+        }
+    }
+
+    // -------- sum_pairwise_manhattan tests --------
+
+    #[test]
+    fn sum_pairwise_manhattan_matches_brute_force() {
+        let points: Vec<Point> = (0..40)
+            .map(|i: i32| Point::new((i * 37) % 23 - 11, (i * i * 7) % 31 - 15))
+            .collect();
+        let brute: i64 = (0..points.len())
+            .flat_map(|i| (i + 1..points.len()).map(move |j| (i, j)))
+            .map(|(i, j)| points[i].manhattan(points[j]) as i64)
+            .sum();
+        assert_eq!(sum_pairwise_manhattan(&points), brute);
+    }
+
+    #[test]
+    fn sum_pairwise_manhattan_small_cases() {
+        assert_eq!(sum_pairwise_manhattan(&[]), 0);
+        assert_eq!(sum_pairwise_manhattan(&[Point::new(3, 4)]), 0);
+        assert_eq!(sum_pairwise_manhattan(&[Point::new(0, 0), Point::new(3, -4)]), 7);
+    }
+
+    // -------- segment_intersection tests --------
+
+    #[test]
+    fn segment_intersection_perpendicular_crossing() {
+        let p = |x, y| Point::new(x, y);
+        assert_eq!(segment_intersection(p(0, 2), p(6, 2), p(3, -1), p(3, 5)), Some(p(3, 2)));
+        assert_eq!(segment_intersection(p(0, 0), p(4, 4), p(0, 4), p(4, 0)), Some(p(2, 2)));
+        // touching at an endpoint counts
+        assert_eq!(segment_intersection(p(0, 0), p(4, 0), p(4, 0), p(4, 3)), Some(p(4, 0)));
+    }
+
+    #[test]
+    fn segment_intersection_none_cases() {
+        let p = |x, y| Point::new(x, y);
+        // parallel
+        assert_eq!(segment_intersection(p(0, 0), p(5, 0), p(0, 1), p(5, 1)), None);
+        // collinear overlap
+        assert_eq!(segment_intersection(p(0, 0), p(5, 0), p(2, 0), p(8, 0)), None);
+        // lines cross but segments stop short
+        assert_eq!(segment_intersection(p(0, 0), p(2, 0), p(3, -1), p(3, 1)), None);
+        // crossing at a non-integer point
+        assert_eq!(segment_intersection(p(0, 0), p(1, 1), p(0, 1), p(1, 0)), None);
+    }
+
+    // -------- row / column tests --------
+
+    #[test]
+    fn column_extracts_middle_elements() {
+        let grid = vec![vec![1, 2, 3], vec![4, 5, 6]];
+        assert_eq!(grid.column(1), vec![&2, &5]);
+        assert!(grid.column(3).is_empty());
+        assert!(grid.column(-1).is_empty());
+    }
+
+    #[test]
+    fn row_and_iterators() {
+        let grid = vec![vec![1, 2, 3], vec![4, 5, 6]];
+        assert_eq!(grid.row(1), Some(&[4, 5, 6][..]));
+        assert_eq!(grid.row(2), None);
+        assert_eq!(grid.row(-1), None);
+        assert_eq!(grid.rows().count(), 2);
+        let cols: Vec<Vec<&i32>> = grid.columns().collect();
+        assert_eq!(cols, vec![vec![&1, &4], vec![&2, &5], vec![&3, &6]]);
+    }
+
+    // -------- turn_cw / turn_ccw tests --------
+
+    #[test]
+    fn turn_cw_four_times_is_identity() {
+        let h = Point::new(0, -1);
+        assert_eq!(h.turn_cw().turn_cw().turn_cw().turn_cw(), h);
+        assert_eq!(h.turn_ccw().turn_cw(), h);
+    }
+
+    #[test]
+    fn turn_cw_heading_sequence_and_walk() {
+        let mut heading = Point::new(0, -1);
+        let mut seen = Vec::new();
+        let mut pos = Point::new(0, 0);
+        for _ in 0..4 {
+            seen.push(heading);
+            pos = pos + heading;
+            heading = heading.turn_cw();
+        }
+        // up, right, down, left with y pointing down
+        assert_eq!(seen, vec![Point::new(0, -1), Point::new(1, 0), Point::new(0, 1), Point::new(-1, 0)]);
+        assert_eq!(pos, Point::new(0, 0));
+        assert_eq!(Point::new(5, 2) - Point::new(1, 3), Point::new(4, -1));
+    }
+
+    // -------- grid_dijkstra tests --------
+
+    #[test]
+    fn grid_dijkstra_avoids_expensive_cells() {
+        let grid: Vec<Vec<u8>> = "\
+1163751742
+1381373672
+2136511328
+3694931569
+7463417111
+1319128137
+1359912421
+3125421639
+1293138521
+2311944581"
+            .lines()
+            .map(|l| l.bytes().map(|b| b - b'0').collect())
+            .collect();
+        let goal = Point::new(9, 9);
+
+        let (total, path) = grid_dijkstra(&grid, Point::new(0, 0), goal, |&v| v as i64).unwrap();
+        assert_eq!(total, 40);
+        assert_eq!((path[0], *path.last().unwrap()), (Point::new(0, 0), goal));
+        let summed: i64 = path[1..].iter().map(|p| grid[p.y as usize][p.x as usize] as i64).sum();
+        assert_eq!(summed, 40);
+    }
+
+    #[test]
+    fn grid_dijkstra_avoids_expensive_cell_and_rejects_out_of_bounds_goal() {
+        let grid = vec![vec![1, 9, 1], vec![1, 1, 1]];
+        let (total, _) = grid_dijkstra(&grid, Point::new(0, 0), Point::new(2, 0), |&v| v as i64).unwrap();
+        assert_eq!(total, 4);
+        assert_eq!(grid_dijkstra(&grid, Point::new(0, 0), Point::new(5, 0), |&v| v as i64), None);
+    }
 }
@@ -1,8 +1,11 @@
+use std::collections::HashMap;
 use std::fs;
-use std::io::{self, Read};
+use std::io::{self, BufRead, BufReader, Read};
 use std::path::Path;
 use std::str::FromStr;
 
+use crate::grid::{Direction, Point};
+
 #[derive(Debug, thiserror::Error)]
 pub enum InputError {
     #[error("io error: {0}")]
@@ -23,6 +26,14 @@ pub fn read_stdin() -> Result<String, InputError> {
     Ok(buf)
 }
 
+/// Lazily read a file line by line, without loading it all into memory.
+pub fn read_lines(
+    path: impl AsRef<Path>,
+) -> Result<impl Iterator<Item = Result<String, InputError>>, InputError> {
+    let file = fs::File::open(path)?;
+    Ok(BufReader::new(file).lines().map(|l| l.map_err(InputError::from)))
+}
+
 /// Split string into lines and parse each line into T.
 pub fn parse_lines<T>(s: &str) -> Result<Vec<T>, InputError>
 where
@@ -44,6 +55,44 @@ pub fn groups(s: &str) -> Vec<&str> {
     s.split("\n\n").collect()
 }
 
+/// Split `items` into consecutive groups of `size`; the last may be shorter.
+/// Panics if `size` is 0.
+pub fn chunks<T: Clone>(items: &[T], size: usize) -> Vec<Vec<T>> {
+    items.chunks(size).map(|c| c.to_vec()).collect()
+}
+
+/// Group the lines of `s` into fixed-size records of `size` lines each.
+pub fn line_chunks(s: &str, size: usize) -> Vec<Vec<&str>> {
+    let lines: Vec<&str> = s.lines().collect();
+    chunks(&lines, size)
+}
+
+/// Split on an arbitrary separator such as "---", trimming each piece.
+/// With `drop_empty`, pieces that are empty after trimming are left out.
+pub fn split_on<'a>(s: &'a str, sep: &str, drop_empty: bool) -> Vec<&'a str> {
+    s.split(sep)
+        .map(str::trim)
+        .filter(|p| !drop_empty || !p.is_empty())
+        .collect()
+}
+
+/// Parse each blank-line-separated block (see `groups`) with `f`.
+/// Blocks are trimmed and empty ones skipped; errors name the failing record.
+pub fn parse_records<T, F>(s: &str, f: F) -> Result<Vec<T>, InputError>
+where
+    F: Fn(&str) -> Result<T, String>,
+{
+    groups(s)
+        .into_iter()
+        .map(str::trim)
+        .filter(|g| !g.is_empty())
+        .enumerate()
+        .map(|(i, g)| {
+            f(g).map_err(|e| InputError::Parse(format!("failed to parse record {i}: {e}")))
+        })
+        .collect()
+}
+
 /// Parse a single comma- (or custom) separated line into Vec<T>.
 pub fn parse_separated<T>(s: &str, sep: char) -> Result<Vec<T>, InputError>
 where
@@ -61,11 +110,129 @@ where
         .collect()
 }
 
+/// Every integer embedded in arbitrary text, e.g. "x=-2, y=18" -> [-2, 18].
+/// A `-` directly before a digit is taken as a sign (like the regex `-?\d+`).
+/// Numbers that don't fit in i64 are skipped.
+pub fn extract_ints(s: &str) -> Vec<i64> {
+    let bytes = s.as_bytes();
+    let mut out = Vec::new();
+    let mut i = 0;
+
+    while i < bytes.len() {
+        let neg = bytes[i] == b'-' && bytes.get(i + 1).is_some_and(u8::is_ascii_digit);
+        if neg || bytes[i].is_ascii_digit() {
+            let start = i;
+            i += 1;
+            while i < bytes.len() && bytes[i].is_ascii_digit() {
+                i += 1;
+            }
+            if let Ok(n) = s[start..i].parse() {
+                out.push(n);
+            }
+        } else {
+            i += 1;
+        }
+    }
+
+    out
+}
+
+/// Every run of digits in arbitrary text as u64; `-` is just a separator.
+/// Numbers that don't fit in u64 are skipped.
+pub fn extract_uints(s: &str) -> Vec<u64> {
+    s.split(|c: char| !c.is_ascii_digit())
+        .filter(|p| !p.is_empty())
+        .filter_map(|p| p.parse().ok())
+        .collect()
+}
+
 /// Parse a grid of characters into Vec<Vec<char>>.
 pub fn char_grid(s: &str) -> Vec<Vec<char>> {
     s.lines().filter(|l| !l.is_empty()).map(|l| l.chars().collect()).collect()
 }
 
+/// Like `char_grid`, but keeps empty lines as rows and pads every row with
+/// spaces to the width of the longest line, so the result is rectangular.
+pub fn char_grid_keep_blanks(s: &str) -> Vec<Vec<char>> {
+    let width = s.lines().map(|l| l.chars().count()).max().unwrap_or(0);
+    s.lines()
+        .map(|l| {
+            let mut row: Vec<char> = l.chars().collect();
+            row.resize(width, ' ');
+            row
+        })
+        .collect()
+}
+
+/// Parse a grid of `sep`-separated integers, one row per line, using
+/// `parse_separated`. Empty lines are skipped like in `char_grid`.
+pub fn int_grid(s: &str, sep: char) -> Result<Vec<Vec<i64>>, InputError> {
+    s.lines()
+        .filter(|l| !l.trim().is_empty())
+        .map(|l| parse_separated(l, sep))
+        .collect()
+}
+
+/// Columns of the input read top to bottom, as strings. Short lines are padded
+/// with spaces first (see `char_grid_keep_blanks`), so every column has one
+/// character per input line.
+pub fn transpose_lines(s: &str) -> Vec<String> {
+    let grid = char_grid_keep_blanks(s);
+    let width = grid.first().map_or(0, |r| r.len());
+    (0..width).map(|x| grid.iter().map(|row| row[x]).collect()).collect()
+}
+
+/// Parse a grid of characters into a map keyed by Point (x = column, y = row).
+/// Rows are numbered like `char_grid`, i.e. after dropping empty lines.
+pub fn char_grid_map(s: &str) -> HashMap<Point, char> {
+    s.lines()
+        .filter(|l| !l.is_empty())
+        .enumerate()
+        .flat_map(|(y, l)| {
+            l.chars()
+                .enumerate()
+                .map(move |(x, c)| (Point::new(x as i32, y as i32), c))
+        })
+        .collect()
+}
+
+/// Like `char_grid_map`, but leaves out cells equal to `skip` (e.g. '.').
+pub fn char_grid_map_filtered(s: &str, skip: char) -> HashMap<Point, char> {
+    let mut map = char_grid_map(s);
+    map.retain(|_, c| *c != skip);
+    map
+}
+
+/// Split on whitespace and parse each token as i64, allowing a leading `+` or `-`.
+pub fn parse_signed_tokens(s: &str) -> Result<Vec<i64>, InputError> {
+    s.split_whitespace()
+        .map(|tok| {
+            tok.parse::<i64>().map_err(|e| {
+                InputError::Parse(format!("failed to parse '{tok}': {e}"))
+            })
+        })
+        .collect()
+}
+
+/// Parse lines like `R 5` into `(Direction, amount)`; anything after the
+/// amount (e.g. a colour code) is ignored.
+pub fn parse_instructions(s: &str) -> Result<Vec<(Direction, i32)>, InputError> {
+    s.lines()
+        .filter(|l| !l.trim().is_empty())
+        .map(|line| {
+            let bad = || InputError::Parse(format!("bad instruction '{line}'"));
+            let mut parts = line.split_whitespace();
+            let mut dir = parts.next().ok_or_else(bad)?.chars();
+            let d = match (dir.next(), dir.next()) {
+                (Some(c), None) => Direction::from_char(c).ok_or_else(bad)?,
+                _ => return Err(bad()),
+            };
+            let n = parts.next().ok_or_else(bad)?.parse().map_err(|_| bad())?;
+            Ok((d, n))
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -117,6 +284,33 @@ mod tests {
     // It's usually better covered by an integration test that runs the binary
     // and pipes data on stdin, so we don't unit-test it here.
 
+    // ---- read_lines tests ----
+
+    #[test]
+    fn read_lines_yields_lines_in_order() -> Result<(), InputError> {
+        let mut path = env::temp_dir();
+        path.push("aoc_utils_test_read_lines.txt");
+
+        let mut file = File::create(&path).expect("failed to create temp file");
+        write!(file, "first\nsecond\n\nfourth\n").expect("failed to write temp file");
+
+        let lines = read_lines(&path)?.collect::<Result<Vec<_>, _>>()?;
+        assert_eq!(lines, vec!["first", "second", "", "fourth"]);
+
+        fs::remove_file(&path).ok();
+
+        Ok(())
+    }
+
+    #[test]
+    fn read_lines_missing_file_returns_io_error() {
+        match read_lines("this_file_should_not_exist_12345.txt") {
+            Err(InputError::Io(_)) => {} // expected
+            Err(other) => panic!("expected InputError::Io, got {other:?}"),
+            Ok(_) => panic!("expected an error for a missing file"),
+        }
+    }
+
     // ---- parse_lines tests ----
 
     #[test]
@@ -156,6 +350,81 @@ mod tests {
         assert_eq!(g, vec!["a", ""]);
     }
 
+    // ---- chunks tests ----
+
+    #[test]
+    fn chunks_last_group_may_be_short() {
+        let items = [1, 2, 3, 4, 5, 6, 7];
+        let sizes: Vec<usize> = chunks(&items, 3).iter().map(|c| c.len()).collect();
+        assert_eq!(sizes, vec![3, 3, 1]);
+        assert_eq!(chunks(&items, 3)[2], vec![7]);
+    }
+
+    #[test]
+    fn line_chunks_groups_lines() {
+        let input = "a\nb\nc\nd\ne\n";
+        assert_eq!(line_chunks(input, 2), vec![vec!["a", "b"], vec!["c", "d"], vec!["e"]]);
+    }
+
+    // ---- split_on tests ----
+
+    #[test]
+    fn split_on_custom_separator_trims_pieces() {
+        let input = "--- scanner 0 ---\n1,2\n---\n3,4\n---\n";
+        assert_eq!(split_on(input, "---", true), vec!["scanner 0", "1,2", "3,4"]);
+    }
+
+    #[test]
+    fn split_on_can_keep_empties() {
+        assert_eq!(split_on("a---b---", "---", false), vec!["a", "b", ""]);
+        assert_eq!(split_on(" a | | b ", "|", true), vec!["a", "b"]);
+    }
+
+    // ---- parse_records tests ----
+
+    #[derive(Debug, PartialEq)]
+    struct Elf {
+        name: String,
+        calories: Vec<u32>,
+    }
+
+    fn parse_elf(block: &str) -> Result<Elf, String> {
+        let mut lines = block.lines();
+        let name = lines.next().ok_or("missing name")?.to_string();
+        let calories = lines
+            .map(|l| l.parse::<u32>().map_err(|e| format!("bad calories '{l}': {e}")))
+            .collect::<Result<_, _>>()?;
+        Ok(Elf { name, calories })
+    }
+
+    #[test]
+    fn parse_records_parses_each_block() {
+        let input = "alice\n100\n200\n\nbob\n300\n";
+        let elves = parse_records(input, parse_elf).expect("parse_records failed");
+
+        assert_eq!(
+            elves,
+            vec![
+                Elf { name: "alice".into(), calories: vec![100, 200] },
+                Elf { name: "bob".into(), calories: vec![300] },
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_records_error_names_record() {
+        let input = "alice\n100\n\nbob\nlots\n";
+        let err = parse_records(input, parse_elf).unwrap_err();
+
+        match err {
+            InputError::Parse(msg) => {
+                assert!(msg.contains("record 1"), "error did not name record: {msg}");
+                assert!(msg.contains("lots"), "error did not include inner message: {msg}");
+            }
+            other => panic!("expected InputError::Parse, got {other:?}"),
+        }
+    }
+
     // ---- parse_separated tests ----
 
     #[test]
@@ -178,6 +447,32 @@ mod tests {
         }
     }
 
+    // ---- extract_ints / extract_uints tests ----
+
+    #[test]
+    fn extract_ints_handles_negatives_in_prose() {
+        assert_eq!(extract_ints("x=-2, y=18: d=7"), vec![-2, 18, 7]);
+        assert_eq!(
+            extract_ints("Sensor at x=2, y=18: closest beacon is at x=-2, y=15"),
+            vec![2, 18, -2, 15]
+        );
+    }
+
+    #[test]
+    fn extract_ints_edge_cases() {
+        assert_eq!(extract_ints(""), Vec::<i64>::new());
+        assert_eq!(extract_ints("no numbers - here"), Vec::<i64>::new());
+        assert_eq!(extract_ints("3-4"), vec![3, -4]);
+        assert_eq!(extract_ints("--5"), vec![-5]);
+        assert_eq!(extract_ints("-9223372036854775808"), vec![i64::MIN]);
+    }
+
+    #[test]
+    fn extract_uints_treats_minus_as_separator() {
+        assert_eq!(extract_uints("x=-2, y=18: d=7"), vec![2, 18, 7]);
+        assert_eq!(extract_uints("1-3 a: abcde"), vec![1, 3]);
+    }
+
     // ---- char_grid tests ----
 
     #[test]
@@ -193,4 +488,110 @@ mod tests {
             ]
         );
     }
+
+    // ---- char_grid_keep_blanks tests ----
+
+    #[test]
+    fn char_grid_keep_blanks_preserves_blank_row_and_pads() {
+        let input = "ab\n\nabc\n";
+        let grid = char_grid_keep_blanks(input);
+
+        assert_eq!(
+            grid,
+            vec![
+                vec!['a', 'b', ' '],
+                vec![' ', ' ', ' '],
+                vec!['a', 'b', 'c'],
+            ]
+        );
+        assert!(char_grid_keep_blanks("").is_empty());
+    }
+
+    // ---- int_grid tests ----
+
+    #[test]
+    fn int_grid_parses_rows() {
+        let grid = int_grid("1 2 3\n\n 4  5 -6\n", ' ').unwrap();
+        assert_eq!(grid, vec![vec![1, 2, 3], vec![4, 5, -6]]);
+        assert_eq!(int_grid("7,8\n9,10", ',').unwrap(), vec![vec![7, 8], vec![9, 10]]);
+    }
+
+    #[test]
+    fn int_grid_reports_bad_token() {
+        assert!(matches!(int_grid("1 2\n3 x", ' '), Err(InputError::Parse(_))));
+    }
+
+    // ---- transpose_lines tests ----
+
+    #[test]
+    fn transpose_lines_reads_columns() {
+        assert_eq!(transpose_lines("ab\ncd"), vec!["ac", "bd"]);
+        assert_eq!(transpose_lines("123\n4\n"), vec!["14", "2 ", "3 "]);
+        assert!(transpose_lines("").is_empty());
+    }
+
+    // ---- parse_signed_tokens tests ----
+
+    #[test]
+    fn parse_signed_tokens_handles_plus_and_minus() {
+        let nums = parse_signed_tokens("+3 -4 5").expect("parse_signed_tokens failed");
+        assert_eq!(nums, vec![3, -4, 5]);
+    }
+
+    #[test]
+    fn parse_signed_tokens_spans_lines() {
+        let nums = parse_signed_tokens("+1\n  -2 +3\n").expect("parse_signed_tokens failed");
+        assert_eq!(nums, vec![1, -2, 3]);
+    }
+
+    #[test]
+    fn parse_signed_tokens_rejects_double_sign() {
+        let err = parse_signed_tokens("+3 +-4").unwrap_err();
+
+        match err {
+            InputError::Parse(msg) => {
+                assert!(msg.contains("+-4"), "error message did not mention bad token: {msg}");
+            }
+            other => panic!("expected InputError::Parse, got {other:?}"),
+        }
+    }
+
+    // ---- parse_instructions tests ----
+
+    #[test]
+    fn parse_instructions_reads_direction_and_amount() {
+        use crate::grid::Direction::*;
+        assert_eq!(parse_instructions("R 4\nU 2").unwrap(), vec![(East, 4), (North, 2)]);
+        assert_eq!(parse_instructions("L 3 (#70c710)\n").unwrap(), vec![(West, 3)]);
+    }
+
+    #[test]
+    fn parse_instructions_rejects_malformed_lines() {
+        for bad in ["X 4", "R", "R four", "RU 1"] {
+            assert!(matches!(parse_instructions(bad), Err(InputError::Parse(_))), "{bad}");
+        }
+    }
+
+    // ---- char_grid_map tests ----
+
+    #[test]
+    fn char_grid_map_keys_by_point() {
+        let input = "#.a\n..b\n";
+        let map = char_grid_map(input);
+
+        assert_eq!(map.len(), 6);
+        assert_eq!(map.get(&Point::new(0, 0)), Some(&'#'));
+        assert_eq!(map.get(&Point::new(2, 1)), Some(&'b'));
+        assert_eq!(map.get(&Point::new(3, 0)), None);
+    }
+
+    #[test]
+    fn char_grid_map_filtered_skips_empty_char() {
+        let input = "#.a\n..b\n";
+        let map = char_grid_map_filtered(input, '.');
+
+        assert_eq!(map.len(), 3);
+        assert_eq!(map.get(&Point::new(2, 0)), Some(&'a'));
+        assert!(!map.contains_key(&Point::new(1, 0)));
+    }
 }
@@ -2,10 +2,18 @@ pub mod input;
 pub mod grid;
 pub mod search;
 pub mod math;
+pub mod puzzles;
+pub mod testing;
+#[cfg(feature = "rayon")]
+pub mod parallel;
 
 pub mod prelude {
     pub use crate::input::*;
     pub use crate::grid::*;
     pub use crate::search::*;
     pub use crate::math::*;
+    pub use crate::puzzles::*;
+    pub use crate::testing::*;
+    #[cfg(feature = "rayon")]
+    pub use crate::parallel::*;
 }
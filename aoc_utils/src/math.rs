@@ -1,3 +1,6 @@
+use crate::grid::Point;
+use crate::search::bfs;
+
 /// Greatest common divisor (Euclidean algorithm).
 pub fn gcd(mut a: i64, mut b: i64) -> i64 {
     while b != 0 {
@@ -18,6 +21,16 @@ pub fn lcm(a: i64, b: i64) -> i64 {
 }
 
 
+/// GCD of all of `nums`; 0 for an empty slice (0 is the identity of gcd).
+pub fn gcd_all(nums: &[i64]) -> i64 {
+    nums.iter().fold(0, |acc, &n| gcd(acc, n))
+}
+
+/// LCM of all of `nums`; 1 for an empty slice (1 is the identity of lcm).
+pub fn lcm_all(nums: &[i64]) -> i64 {
+    nums.iter().fold(1, |acc, &n| lcm(acc, n))
+}
+
 /// Positive modulo: always returns a value in [0, m).
 pub fn pos_mod(mut x: i64, m: i64) -> i64 {
     x %= m;
@@ -27,6 +40,484 @@ pub fn pos_mod(mut x: i64, m: i64) -> i64 {
     x
 }
 
+/// The linear map x -> (a*x + b) mod n, e.g. a card shuffle on a deck of n cards.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LinearCongruence {
+    pub a: i128,
+    pub b: i128,
+    pub n: i128,
+}
+
+impl LinearCongruence {
+    /// Build the map with `a` and `b` reduced into [0, n).
+    pub fn new(a: i128, b: i128, n: i128) -> Self {
+        Self { a: a.rem_euclid(n), b: b.rem_euclid(n), n }
+    }
+
+    /// The identity map modulo `n`.
+    pub fn identity(n: i128) -> Self {
+        Self::new(1, 0, n)
+    }
+
+    /// Apply the map to `x`.
+    pub fn apply(&self, x: i128) -> i128 {
+        (self.a * x + self.b).rem_euclid(self.n)
+    }
+
+    /// The map that applies `self` first and then `next`.
+    pub fn compose(&self, next: &LinearCongruence) -> Self {
+        debug_assert_eq!(self.n, next.n, "composing maps with different moduli");
+        Self::new(next.a * self.a, next.a * self.b + next.b, self.n)
+    }
+
+    /// The map applied `k` times in a row, by repeated squaring.
+    pub fn pow(&self, mut k: u64) -> Self {
+        let mut result = Self::identity(self.n);
+        let mut base = *self;
+        while k > 0 {
+            if k & 1 == 1 {
+                result = result.compose(&base);
+            }
+            base = base.compose(&base);
+            k >>= 1;
+        }
+        result
+    }
+}
+
+/// Smallest `x` in `[lo, hi]` for which `pred(x)` is true.
+///
+/// Assumes `pred` is monotonic over the range (false...false, true...true).
+/// Returns None if the range is empty or `pred` is never true in it.
+pub fn binary_search_first_true<F: FnMut(i64) -> bool>(mut lo: i64, mut hi: i64, mut pred: F) -> Option<i64> {
+    if lo > hi || !pred(hi) {
+        return None;
+    }
+    // invariant: pred(hi) is true, answer is in [lo, hi]
+    while lo < hi {
+        let mid = ((lo as i128 + hi as i128) >> 1) as i64; // floor, no overflow
+        if pred(mid) {
+            hi = mid;
+        } else {
+            lo = mid + 1;
+        }
+    }
+    Some(lo)
+}
+
+/// Area of a simple polygon given its vertices in order (either winding), via
+/// the shoelace formula. Polygons whose area is a half-integer are rounded
+/// down; rectilinear lattice polygons always have whole areas.
+pub fn polygon_area(points: &[Point]) -> i64 {
+    let n = points.len();
+    let twice: i64 = (0..n)
+        .map(|i| {
+            let (a, b) = (points[i], points[(i + 1) % n]);
+            a.x as i64 * b.y as i64 - b.x as i64 * a.y as i64
+        })
+        .sum();
+    twice.abs() / 2
+}
+
+/// Pick's theorem: the number of interior lattice points of a lattice polygon
+/// with the given `area` and number of `boundary` lattice points
+/// (A = i + b/2 - 1, so i = A - b/2 + 1).
+pub fn interior_points(area: i64, boundary: i64) -> i64 {
+    area - boundary / 2 + 1
+}
+
+/// An inclusive range of integers `start..=end`; empty if `end < start`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Interval {
+    pub start: i64,
+    pub end: i64,
+}
+
+impl Interval {
+    pub fn new(start: i64, end: i64) -> Self {
+        Self { start, end }
+    }
+
+    /// Number of integers covered (0 if empty).
+    pub fn len(&self) -> i64 {
+        (self.end - self.start + 1).max(0)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.end < self.start
+    }
+
+    /// The overlap of `a` and `b`, if any.
+    pub fn intersect(a: Interval, b: Interval) -> Option<Interval> {
+        let i = Interval::new(a.start.max(b.start), a.end.min(b.end));
+        (!i.is_empty()).then_some(i)
+    }
+
+    /// Sort `intervals` and coalesce overlapping or adjacent ones in place
+    /// (1..=3 and 4..=5 become 1..=5). Empty intervals are dropped.
+    pub fn merge(intervals: &mut Vec<Interval>) {
+        intervals.retain(|i| !i.is_empty());
+        intervals.sort_unstable();
+        let mut out: Vec<Interval> = Vec::with_capacity(intervals.len());
+        for &i in intervals.iter() {
+            match out.last_mut() {
+                Some(last) if i.start <= last.end.saturating_add(1) => last.end = last.end.max(i.end),
+                _ => out.push(i),
+            }
+        }
+        *intervals = out;
+    }
+}
+
+/// A 2x2 integer matrix, enough for Fibonacci-like linear recurrences.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Matrix2(pub [[i64; 2]; 2]);
+
+impl Matrix2 {
+    pub const IDENTITY: Matrix2 = Matrix2([[1, 0], [0, 1]]);
+
+    /// Matrix product with every entry reduced into [0, m) via `pos_mod`.
+    pub fn mul_mod(self, other: Matrix2, m: i64) -> Matrix2 {
+        let (a, b) = (self.0, other.0);
+        let mut out = [[0; 2]; 2];
+        for (i, row) in out.iter_mut().enumerate() {
+            for (j, cell) in row.iter_mut().enumerate() {
+                // i128 so the products can't overflow before reducing
+                let sum = a[i][0] as i128 * b[0][j] as i128 + a[i][1] as i128 * b[1][j] as i128;
+                *cell = pos_mod((sum % m as i128) as i64, m);
+            }
+        }
+        Matrix2(out)
+    }
+
+    /// `self` raised to `exp` modulo `m`, by repeated squaring.
+    pub fn pow_mod(self, mut exp: u64, m: i64) -> Matrix2 {
+        let mut result = Matrix2([[1 % m, 0], [0, 1 % m]]);
+        let mut base = self;
+        while exp > 0 {
+            if exp & 1 == 1 {
+                result = result.mul_mod(base, m);
+            }
+            base = base.mul_mod(base, m);
+            exp >>= 1;
+        }
+        result
+    }
+}
+
+/// `base^exp mod m`, by repeated squaring. Result is in [0, m).
+pub fn mod_pow(base: i64, mut exp: u64, m: i64) -> i64 {
+    let m128 = m as i128;
+    let mut base = pos_mod(base, m) as i128;
+    let mut result = 1 % m128;
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = result * base % m128;
+        }
+        base = base * base % m128;
+        exp >>= 1;
+    }
+    result as i64
+}
+
+/// Modular inverse of `a` mod `m` (extended Euclid), if gcd(a, m) == 1.
+pub fn mod_inverse(a: i64, m: i64) -> Option<i64> {
+    let (mut old_r, mut r) = (pos_mod(a, m) as i128, m as i128);
+    let (mut old_s, mut s) = (1_i128, 0_i128);
+    while r != 0 {
+        let q = old_r / r;
+        (old_r, r) = (r, old_r - q * r);
+        (old_s, s) = (s, old_s - q * s);
+    }
+    if old_r != 1 {
+        return None;
+    }
+    Some(pos_mod((old_s % m as i128) as i64, m))
+}
+
+/// Chinese remainder theorem: the smallest x >= 0 with x = r (mod m) for every
+/// `(r, m)` in `congruences`, together with the product of the moduli. The
+/// moduli must be pairwise coprime and their product must fit in i64 (None
+/// otherwise).
+pub fn crt(congruences: &[(i64, i64)]) -> Option<(i64, i64)> {
+    congruences.iter().try_fold((0_i64, 1_i64), |(x, m), &(r, n)| {
+        // x + m * k = r (mod n)  =>  k = (r - x) * m^-1 (mod n)
+        let inv = mod_inverse(m, n)? as i128;
+        let k = ((r as i128 - x as i128) * inv).rem_euclid(n as i128);
+        let mn = m as i128 * n as i128;
+        let x = (x as i128 + m as i128 * k).rem_euclid(mn);
+        Some((i64::try_from(x).ok()?, i64::try_from(mn).ok()?))
+    })
+}
+
+/// Value at `x` of the polynomial through the sample `points` (distinct x's),
+/// e.g. three samples of an eventually-quadratic sequence. Computed exactly with
+/// i128 fractions; assumes the true value is an integer (otherwise it is
+/// truncated).
+pub fn lagrange_interpolate(points: &[(i64, i64)], x: i64) -> i64 {
+    fn gcd128(a: i128, b: i128) -> i128 {
+        if b == 0 { a.abs() } else { gcd128(b, a % b) }
+    }
+
+    let (mut num, mut den) = (0_i128, 1_i128);
+    for (i, &(xi, yi)) in points.iter().enumerate() {
+        let (mut t_num, mut t_den) = (yi as i128, 1_i128);
+        for (j, &(xj, _)) in points.iter().enumerate() {
+            if i != j {
+                t_num *= (x - xj) as i128;
+                t_den *= (xi - xj) as i128;
+            }
+        }
+        num = num * t_den + t_num * den;
+        den *= t_den;
+        let g = gcd128(num, den).max(1);
+        num /= g;
+        den /= g;
+    }
+    (num / den) as i64
+}
+
+/// Floor of the square root of `n`, exact for all u64 (the float estimate is
+/// corrected by at most a step either way).
+pub fn isqrt(n: u64) -> u64 {
+    let mut r = (n as f64).sqrt() as u64;
+    while r.checked_mul(r).is_none_or(|sq| sq > n) {
+        r -= 1;
+    }
+    while (r + 1).checked_mul(r + 1).is_some_and(|sq| sq <= n) {
+        r += 1;
+    }
+    r
+}
+
+/// True if `n` is the square of an integer.
+pub fn is_perfect_square(n: u64) -> bool {
+    let r = isqrt(n);
+    r * r == n
+}
+
+/// Linearly interpolate `y` at `x` from samples sorted by x, or None if `x`
+/// is outside the sampled range. Non-exact results round toward the left sample.
+pub fn interp_table(table: &[(i64, i64)], x: i64) -> Option<i64> {
+    let i = table.partition_point(|&(tx, _)| tx < x);
+    let &(x1, y1) = table.get(i)?;
+    if x1 == x {
+        return Some(y1);
+    }
+    let &(x0, y0) = table.get(i.checked_sub(1)?)?;
+    let dy = (y1 - y0) as i128 * (x - x0) as i128 / (x1 - x0) as i128;
+    Some(y0 + dy as i64)
+}
+
+/// Primality test, exact for every u64: Miller-Rabin with the first twelve
+/// primes (2..=37) as witnesses, which is known to be deterministic below
+/// about 3.18e23, so for all u64.
+pub fn is_prime(n: u64) -> bool {
+    const WITNESSES: [u64; 12] = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37];
+    if n < 2 {
+        return false;
+    }
+    for p in WITNESSES {
+        if n.is_multiple_of(p) {
+            return n == p;
+        }
+    }
+
+    let mul = |a: u64, b: u64| (a as u128 * b as u128 % n as u128) as u64;
+    let pow = |mut b: u64, mut e: u64| {
+        let mut r = 1;
+        while e > 0 {
+            if e & 1 == 1 {
+                r = mul(r, b);
+            }
+            b = mul(b, b);
+            e >>= 1;
+        }
+        r
+    };
+
+    // n - 1 = d * 2^s with d odd
+    let s = (n - 1).trailing_zeros();
+    let d = (n - 1) >> s;
+    WITNESSES.iter().all(|&a| {
+        let mut x = pow(a, d);
+        if x == 1 || x == n - 1 {
+            return true;
+        }
+        for _ in 1..s {
+            x = mul(x, x);
+            if x == n - 1 {
+                return true;
+            }
+        }
+        false
+    })
+}
+
+/// n! for n <= 20; 21! no longer fits in a u64 and panics.
+pub fn factorial(n: u64) -> u64 {
+    (1..=n).fold(1_u64, |acc, k| acc.checked_mul(k).expect("factorial overflows u64 past 20!"))
+}
+
+/// Binomial coefficient C(n, k); 0 if k > n. Panics if the result overflows
+/// u64 (e.g. C(68, 34)); intermediate products are computed in u128.
+pub fn n_choose_k(n: u64, k: u64) -> u64 {
+    if k > n {
+        return 0;
+    }
+    let k = k.min(n - k);
+    let mut result: u128 = 1;
+    for i in 0..k {
+        // exact: result * (n - i) is divisible by (i + 1) at every step.
+        // result is C(n, i) <= C(n, k), so this only overflows u128 when the
+        // answer can't fit in u64 anyway.
+        result = result.checked_mul((n - i) as u128).expect("n_choose_k overflows u64") / (i + 1) as u128;
+    }
+    u64::try_from(result).expect("n_choose_k overflows u64")
+}
+
+/// Factorials and inverse factorials mod a prime `p` for 0..=max_n, so many
+/// binomials mod `p` can be answered in O(1) each after O(max_n) setup.
+#[derive(Debug, Clone)]
+pub struct FactorialTable {
+    p: u64,
+    fact: Vec<u64>,
+    inv_fact: Vec<u64>,
+}
+
+impl FactorialTable {
+    /// Build the table. Panics unless max_n < p (otherwise n! is 0 mod p), and
+    /// if `max_n!` has no inverse mod `p` (i.e. `p` is not prime).
+    pub fn new(max_n: u64, p: u64) -> Self {
+        assert!(max_n < p, "FactorialTable requires max_n < p, got {max_n} >= {p}");
+        let mul = |a: u64, b: u64| (a as u128 * b as u128 % p as u128) as u64;
+        let n = max_n as usize;
+
+        let mut fact = vec![1 % p; n + 1];
+        for i in 1..=n {
+            fact[i] = mul(fact[i - 1], i as u64);
+        }
+        let mut inv_fact = vec![0; n + 1];
+        inv_fact[n] = mod_inverse(fact[n] as i64, p as i64).expect("factorial not invertible; is p prime?") as u64;
+        for i in (1..=n).rev() {
+            inv_fact[i - 1] = mul(inv_fact[i], i as u64);
+        }
+
+        Self { p, fact, inv_fact }
+    }
+
+    /// n! mod p. Panics if n > max_n.
+    pub fn fact(&self, n: u64) -> u64 {
+        self.fact[n as usize]
+    }
+
+    /// (n!)^-1 mod p. Panics if n > max_n.
+    pub fn inv_fact(&self, n: u64) -> u64 {
+        self.inv_fact[n as usize]
+    }
+
+    /// C(n, k) mod p; 0 if k > n. Panics if n > max_n.
+    pub fn choose(&self, n: u64, k: u64) -> u64 {
+        if k > n {
+            return 0;
+        }
+        let mul = |a: u64, b: u64| (a as u128 * b as u128 % self.p as u128) as u64;
+        mul(mul(self.fact(n), self.inv_fact(k)), self.inv_fact(n - k))
+    }
+}
+
+/// C(n, k) mod a prime `p` for a one-off query; builds a `FactorialTable`,
+/// so it runs in O(n). Panics unless n < p.
+pub fn n_choose_k_mod(n: u64, k: u64, p: u64) -> u64 {
+    FactorialTable::new(n, p).choose(n, k)
+}
+
+/// All submasks of `mask`, from `mask` itself down to 0, in the usual
+/// `sub = (sub - 1) & mask` order.
+pub fn submasks(mask: u32) -> impl Iterator<Item = u32> {
+    std::iter::successors(Some(mask), move |&sub| (sub != 0).then(|| (sub - 1) & mask))
+}
+
+/// Fewest `sets` (bitmasks) whose union covers every bit of `universe`, or
+/// None if even all of them together don't.
+///
+/// Breadth-first over the covered-bits masks, so the state space is up to
+/// 2^(bits in universe) masks times `sets.len()` transitions; practical for
+/// universes of about 20 elements.
+pub fn min_set_cover(universe: u32, sets: &[u32]) -> Option<usize> {
+    let dist = bfs(0_u32, |covered| sets.iter().map(move |&s| covered | (s & universe)));
+    dist.get(&universe).copied()
+}
+
+/// Base-10 digits of `n`, most significant first; `digits(0)` is `[0]`.
+pub fn digits(mut n: u64) -> Vec<u8> {
+    let mut out = vec![(n % 10) as u8];
+    n /= 10;
+    while n > 0 {
+        out.push((n % 10) as u8);
+        n /= 10;
+    }
+    out.reverse();
+    out
+}
+
+/// Sum of the base-10 digits of `n`.
+pub fn digit_sum(mut n: u64) -> u64 {
+    let mut sum = 0;
+    while n > 0 {
+        sum += n % 10;
+        n /= 10;
+    }
+    sum
+}
+
+/// Inverse of `digits`: most significant digit first.
+pub fn from_digits(digits: &[u8]) -> u64 {
+    digits.iter().fold(0, |acc, &d| acc * 10 + d as u64)
+}
+
+/// Rearrange `xs` into the next lexicographic permutation in place.
+///
+/// Returns false if `xs` was already the last (descending) permutation, in
+/// which case it is reset to the first (ascending) one, like C++'s
+/// `std::next_permutation`. Start from sorted input to visit every permutation.
+pub fn next_permutation<T: Ord>(xs: &mut [T]) -> bool {
+    // longest non-increasing suffix starts at i
+    let Some(i) = (1..xs.len()).rev().find(|&i| xs[i - 1] < xs[i]) else {
+        xs.reverse();
+        return false;
+    };
+    // rightmost element greater than the pivot xs[i - 1]
+    let j = (i..xs.len()).rev().find(|&j| xs[j] > xs[i - 1]).unwrap();
+    xs.swap(i - 1, j);
+    xs[i..].reverse();
+    true
+}
+
+/// Digits of `n` in `base`, least significant first (`to_base(6, 2)` is
+/// `[0, 1, 1]`); `to_base(0, b)` is `[0]`. Panics if `base < 2`.
+pub fn to_base(mut n: u64, base: u32) -> Vec<u32> {
+    assert!(base >= 2, "base must be at least 2, got {base}");
+    let b = base as u64;
+    let mut out = vec![(n % b) as u32];
+    n /= b;
+    while n > 0 {
+        out.push((n % b) as u32);
+        n /= b;
+    }
+    out
+}
+
+/// Inverse of `to_base`: `digits` are least significant first.
+/// Panics if `base < 2`.
+pub fn from_base(digits: &[u32], base: u32) -> u64 {
+    assert!(base >= 2, "base must be at least 2, got {base}");
+    digits.iter().rev().fold(0, |acc, &d| {
+        debug_assert!(d < base, "digit {d} out of range for base {base}");
+        acc * base as u64 + d as u64
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -76,6 +567,21 @@ mod tests {
         assert_eq!(lcm(-4, -6), 12);
     }
 
+    // ---- gcd_all / lcm_all tests ----
+
+    #[test]
+    fn gcd_all_and_lcm_all() {
+        assert_eq!(gcd_all(&[12, 18, 24]), 6);
+        assert_eq!(lcm_all(&[4, 6, 8]), 24);
+        assert_eq!(gcd_all(&[-7]), 7);
+    }
+
+    #[test]
+    fn gcd_all_and_lcm_all_empty_identities() {
+        assert_eq!(gcd_all(&[]), 0);
+        assert_eq!(lcm_all(&[]), 1);
+    }
+
     // ---- pos_mod tests ----
 
     #[test]
@@ -97,4 +603,419 @@ mod tests {
         assert_eq!(pos_mod(123456789, 97), 123456789 % 97);
         assert_eq!(pos_mod(-123456789, 97), pos_mod(-(123456789 % 97), 97));
     }
+
+    // ---- LinearCongruence tests ----
+
+    #[test]
+    fn linear_congruence_composes_shuffle_sequence() {
+        // Deck of 10: "deal with increment 7", then "deal into new stack" twice.
+        // Expected deck afterwards: 0 3 6 9 2 5 8 1 4 7
+        let n = 10;
+        let increment = LinearCongruence::new(7, 0, n);
+        let new_stack = LinearCongruence::new(-1, -1, n);
+
+        let shuffle = increment.compose(&new_stack).compose(&new_stack);
+
+        let expected = [0, 3, 6, 9, 2, 5, 8, 1, 4, 7];
+        for (pos, &card) in expected.iter().enumerate() {
+            assert_eq!(shuffle.apply(card), pos as i128, "card {card}");
+        }
+    }
+
+    #[test]
+    fn linear_congruence_compose_order_matters() {
+        // cut 3 then new stack vs new stack then cut 3
+        let n = 10;
+        let cut = LinearCongruence::new(1, -3, n);
+        let new_stack = LinearCongruence::new(-1, -1, n);
+
+        assert_eq!(cut.compose(&new_stack).apply(5), new_stack.apply(cut.apply(5)));
+        assert_eq!(new_stack.compose(&cut).apply(5), cut.apply(new_stack.apply(5)));
+    }
+
+    #[test]
+    fn linear_congruence_pow_matches_repeated_application() {
+        let f = LinearCongruence::new(7, 4, 10007);
+        let mut x = 2019;
+        for _ in 0..123 {
+            x = f.apply(x);
+        }
+        assert_eq!(f.pow(123).apply(2019), x);
+        assert_eq!(f.pow(0), LinearCongruence::identity(10007));
+    }
+
+    // ---- binary_search_first_true tests ----
+
+    #[test]
+    fn binary_search_first_true_finds_boundary() {
+        assert_eq!(binary_search_first_true(0, 1000, |x| x * x >= 100), Some(10));
+        assert_eq!(binary_search_first_true(0, 1000, |x| x * x >= 101), Some(11));
+    }
+
+    #[test]
+    fn binary_search_first_true_edges() {
+        // true everywhere -> lo
+        assert_eq!(binary_search_first_true(-5, 5, |_| true), Some(-5));
+        // never true -> None
+        assert_eq!(binary_search_first_true(0, 10, |x| x > 10), None);
+        // empty range
+        assert_eq!(binary_search_first_true(3, 2, |_| true), None);
+        // huge range does not overflow
+        assert_eq!(binary_search_first_true(i64::MIN, i64::MAX, |x| x >= 42), Some(42));
+    }
+
+    // ---- polygon_area / interior_points tests ----
+
+    #[test]
+    fn polygon_area_unit_square_and_triangle() {
+        let square = [Point::new(0, 0), Point::new(1, 0), Point::new(1, 1), Point::new(0, 1)];
+        assert_eq!(polygon_area(&square), 1);
+
+        // right triangle with legs 4 and 3: area 6, either winding
+        let tri = [Point::new(0, 0), Point::new(4, 0), Point::new(0, 3)];
+        assert_eq!(polygon_area(&tri), 6);
+        let rev: Vec<Point> = tri.iter().rev().copied().collect();
+        assert_eq!(polygon_area(&rev), 6);
+    }
+
+    #[test]
+    fn interior_points_matches_hand_count() {
+        // same triangle: boundary = 4 + 3 + gcd(4, 3) = 8,
+        // interior points (1,1), (2,1), (1,2)
+        assert_eq!(interior_points(6, 8), 3);
+        // 2x2 square: 8 boundary points, 1 interior
+        assert_eq!(interior_points(4, 8), 1);
+    }
+
+    // ---- Interval tests ----
+
+    #[test]
+    fn interval_merge_coalesces_overlaps() {
+        let mut v = vec![Interval::new(7, 8), Interval::new(1, 3), Interval::new(2, 5)];
+        Interval::merge(&mut v);
+        assert_eq!(v, vec![Interval::new(1, 5), Interval::new(7, 8)]);
+
+        let mut adjacent = vec![Interval::new(4, 5), Interval::new(1, 3), Interval::new(9, 2)];
+        Interval::merge(&mut adjacent);
+        assert_eq!(adjacent, vec![Interval::new(1, 5)]);
+    }
+
+    #[test]
+    fn interval_intersect_and_len() {
+        let (a, b) = (Interval::new(1, 5), Interval::new(4, 10));
+        assert_eq!(Interval::intersect(a, b), Some(Interval::new(4, 5)));
+        assert_eq!(Interval::intersect(a, Interval::new(6, 7)), None);
+        assert_eq!(a.len(), 5);
+        assert_eq!(Interval::new(3, 2).len(), 0);
+        assert!(Interval::new(3, 2).is_empty());
+    }
+
+    // ---- Matrix2 tests ----
+
+    #[test]
+    fn matrix2_fibonacci_power() {
+        // [[1, 1], [1, 0]]^n = [[F(n+1), F(n)], [F(n), F(n-1)]]
+        let fib = Matrix2([[1, 1], [1, 0]]);
+
+        assert_eq!(fib.pow_mod(10, 1_000_000).0[0][1], 55);
+        assert_eq!(fib.pow_mod(40, 1_000_000_007).0[0][1], 102_334_155);
+        // F(90) = 2880067194370816120, reduced
+        assert_eq!(fib.pow_mod(90, 1_000_000_007).0[0][1], 2_880_067_194_370_816_120 % 1_000_000_007);
+    }
+
+    #[test]
+    fn matrix2_mul_mod_reduces_negatives() {
+        let a = Matrix2([[-1, 2], [3, -4]]);
+        assert_eq!(a.mul_mod(Matrix2::IDENTITY, 5), Matrix2([[4, 2], [3, 1]]));
+        assert_eq!(a.pow_mod(0, 7), Matrix2::IDENTITY);
+    }
+
+    #[test]
+    fn matrix2_pow_mod_with_modulus_one_is_zero() {
+        let m = Matrix2([[1, 1], [1, 0]]);
+        assert_eq!(m.pow_mod(0, 1), Matrix2([[0, 0], [0, 0]]));
+        assert_eq!(m.pow_mod(5, 1), Matrix2([[0, 0], [0, 0]]));
+    }
+
+    // ---- mod_pow / mod_inverse tests ----
+
+    #[test]
+    fn mod_pow_basic() {
+        assert_eq!(mod_pow(2, 10, 1000), 24);
+        assert_eq!(mod_pow(-2, 3, 7), pos_mod(-8, 7));
+        assert_eq!(mod_pow(5, 0, 13), 1);
+        assert_eq!(mod_pow(5, 3, 1), 0);
+    }
+
+    #[test]
+    fn mod_inverse_basic() {
+        assert_eq!(mod_inverse(3, 11), Some(4)); // 3 * 4 = 12 = 1 mod 11
+        assert_eq!(mod_inverse(-3, 11), Some(7));
+        assert_eq!(mod_inverse(6, 9), None); // not coprime
+    }
+
+    // ---- crt tests ----
+
+    #[test]
+    fn crt_solves_coprime_system() {
+        assert_eq!(crt(&[(2, 3), (3, 5), (2, 7)]), Some((23, 105)));
+        assert_eq!(crt(&[(-1, 4)]), Some((3, 4)));
+        assert_eq!(crt(&[]), Some((0, 1)));
+    }
+
+    #[test]
+    fn crt_rejects_shared_factors() {
+        assert_eq!(crt(&[(1, 4), (3, 6)]), None);
+    }
+
+    #[test]
+    fn crt_rejects_modulus_product_overflowing_i64() {
+        // 1_000_000_007 * 998_244_353 * 1_000_000_009 > i64::MAX
+        assert_eq!(crt(&[(1, 1_000_000_007), (2, 998_244_353), (3, 1_000_000_009)]), None);
+        assert!(crt(&[(1, 1_000_000_007), (2, 998_244_353)]).is_some());
+    }
+
+    // ---- lagrange_interpolate tests ----
+
+    #[test]
+    fn lagrange_interpolate_quadratic() {
+        let pts = [(1, 1), (2, 4), (3, 9)];
+        assert_eq!(lagrange_interpolate(&pts, 4), 16);
+        assert_eq!(lagrange_interpolate(&pts, -10), 100);
+        assert_eq!(lagrange_interpolate(&pts, 2), 4);
+    }
+
+    #[test]
+    fn lagrange_interpolate_garden_style_extrapolation() {
+        // samples at 65, 65 + 131, 65 + 2 * 131 of 3x^2 - 2x + 7
+        let f = |x: i64| 3 * x * x - 2 * x + 7;
+        let pts = [(65, f(65)), (196, f(196)), (327, f(327))];
+        assert_eq!(lagrange_interpolate(&pts, 26_501_365), f(26_501_365));
+    }
+
+    // ---- isqrt tests ----
+
+    #[test]
+    fn isqrt_small_values() {
+        assert_eq!(isqrt(0), 0);
+        assert_eq!(isqrt(15), 3);
+        assert_eq!(isqrt(16), 4);
+        assert!(is_perfect_square(144));
+        assert!(!is_perfect_square(145));
+    }
+
+    #[test]
+    fn isqrt_near_float_precision_limits() {
+        assert_eq!(isqrt(u64::MAX), 4_294_967_295);
+        let r = 3_037_000_499_u64;
+        assert_eq!(isqrt(r * r), r);
+        assert_eq!(isqrt(r * r - 1), r - 1);
+        assert!(!is_perfect_square(r * r - 1));
+    }
+
+    // ---- interp_table tests ----
+
+    #[test]
+    fn interp_table_between_samples() {
+        let table = [(0, 10), (10, 30), (20, 0)];
+        assert_eq!(interp_table(&table, 5), Some(20));
+        assert_eq!(interp_table(&table, 15), Some(15));
+        assert_eq!(interp_table(&table, 10), Some(30));
+        assert_eq!(interp_table(&table, 0), Some(10));
+        assert_eq!(interp_table(&table, 20), Some(0));
+    }
+
+    #[test]
+    fn interp_table_outside_range_is_none() {
+        assert_eq!(interp_table(&[(0, 1), (4, 5)], -1), None);
+        assert_eq!(interp_table(&[(0, 1), (4, 5)], 5), None);
+        assert_eq!(interp_table(&[], 0), None);
+    }
+
+    // ---- is_prime tests ----
+
+    #[test]
+    fn is_prime_small_values_match_trial_division() {
+        let trial = |n: u64| n >= 2 && (2..n).take_while(|d| d * d <= n).all(|d| !n.is_multiple_of(d));
+        for n in 0..2000 {
+            assert_eq!(is_prime(n), trial(n), "{n}");
+        }
+    }
+
+    #[test]
+    fn is_prime_large_and_carmichael() {
+        assert!(is_prime(1_000_000_007));
+        assert!(is_prime(2_305_843_009_213_693_951)); // 2^61 - 1
+        assert!(is_prime(18_446_744_073_709_551_557)); // largest u64 prime
+        for c in [561, 1105, 1729, 2465, 6601, 3_215_031_751, 1_000_000_007 * 998_244_353] {
+            assert!(!is_prime(c), "{c}");
+        }
+        assert!(!is_prime(u64::MAX));
+    }
+
+    // ---- factorial / n_choose_k tests ----
+
+    #[test]
+    fn factorial_small_and_limit() {
+        assert_eq!(factorial(0), 1);
+        assert_eq!(factorial(5), 120);
+        assert_eq!(factorial(20), 2_432_902_008_176_640_000);
+    }
+
+    #[test]
+    #[should_panic(expected = "overflows")]
+    fn factorial_overflow_panics() {
+        factorial(21);
+    }
+
+    #[test]
+    fn n_choose_k_basic() {
+        assert_eq!(n_choose_k(5, 2), 10);
+        assert_eq!(n_choose_k(5, 0), 1);
+        assert_eq!(n_choose_k(5, 6), 0);
+        assert_eq!(n_choose_k(60, 30), 118_264_581_564_861_424);
+    }
+
+    #[test]
+    #[should_panic(expected = "n_choose_k overflows u64")]
+    fn n_choose_k_overflow_panics_before_u128_wraps() {
+        // intermediate products exceed u128 long before the final division
+        n_choose_k(200, 100);
+    }
+
+    #[test]
+    fn n_choose_k_mod_against_known_value() {
+        assert_eq!(n_choose_k_mod(1000, 500, 1_000_000_007), 159_835_829);
+        assert_eq!(n_choose_k_mod(5, 2, 7), 3);
+        assert_eq!(n_choose_k_mod(5, 9, 7), 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "requires max_n < p")]
+    fn n_choose_k_mod_rejects_n_at_least_p() {
+        n_choose_k_mod(7, 2, 7);
+    }
+
+    #[test]
+    fn factorial_table_answers_many_queries() {
+        let p = 1_000_000_007;
+        let table = FactorialTable::new(1000, p);
+        assert_eq!(table.choose(1000, 500), 159_835_829);
+        assert_eq!(table.choose(60, 30), n_choose_k(60, 30) % p);
+        assert_eq!(table.choose(3, 5), 0);
+        assert_eq!(table.fact(20), factorial(20) % p);
+        assert_eq!(table.fact(123) as u128 * table.inv_fact(123) as u128 % p as u128, 1);
+    }
+
+    // ---- submasks tests ----
+
+    #[test]
+    fn submasks_of_0b101() {
+        let subs: Vec<u32> = submasks(0b101).collect();
+        assert_eq!(subs, vec![0b101, 0b100, 0b001, 0b000]);
+    }
+
+    #[test]
+    fn submasks_counts() {
+        assert_eq!(submasks(0).collect::<Vec<_>>(), vec![0]);
+        assert_eq!(submasks(0b1111).count(), 16);
+        assert!(submasks(0b1011_0110).all(|s| s & !0b1011_0110 == 0));
+    }
+
+    // ---- min_set_cover tests ----
+
+    #[test]
+    fn min_set_cover_finds_optimal_size() {
+        // universe {0..5}; greedy would take 0b011100 first and need 3 sets
+        let universe = 0b11_1111;
+        let sets = [0b01_1100, 0b00_0111, 0b11_1000, 0b00_0011];
+        assert_eq!(min_set_cover(universe, &sets), Some(2));
+    }
+
+    #[test]
+    fn min_set_cover_edge_cases() {
+        assert_eq!(min_set_cover(0, &[]), Some(0));
+        assert_eq!(min_set_cover(0b111, &[0b001, 0b010]), None);
+        assert_eq!(min_set_cover(0b11, &[0b1111]), Some(1)); // extra bits ignored
+    }
+
+    // ---- digits tests ----
+
+    #[test]
+    fn digits_and_round_trip() {
+        assert_eq!(digits(12345), vec![1, 2, 3, 4, 5]);
+        assert_eq!(from_digits(&digits(12345)), 12345);
+        assert_eq!(from_digits(&digits(u64::MAX)), u64::MAX);
+    }
+
+    #[test]
+    fn digits_of_zero_and_digit_sum() {
+        assert_eq!(digits(0), vec![0]);
+        assert_eq!(digits(100), vec![1, 0, 0]);
+        assert_eq!(digit_sum(0), 0);
+        assert_eq!(digit_sum(12345), 15);
+        assert_eq!(from_digits(&[]), 0);
+    }
+
+    // ---- next_permutation tests ----
+
+    #[test]
+    fn next_permutation_visits_all_of_1_2_3() {
+        let mut xs = [1, 2, 3];
+        let mut seen = vec![xs.to_vec()];
+        while next_permutation(&mut xs) {
+            seen.push(xs.to_vec());
+        }
+
+        assert_eq!(seen.len(), 6);
+        assert_eq!(
+            seen,
+            vec![
+                vec![1, 2, 3],
+                vec![1, 3, 2],
+                vec![2, 1, 3],
+                vec![2, 3, 1],
+                vec![3, 1, 2],
+                vec![3, 2, 1],
+            ]
+        );
+        // wrapped back to the first permutation
+        assert_eq!(xs, [1, 2, 3]);
+    }
+
+    #[test]
+    fn next_permutation_with_duplicates_and_trivial() {
+        let mut xs = [1, 1, 2];
+        let mut count = 1;
+        while next_permutation(&mut xs) {
+            count += 1;
+        }
+        assert_eq!(count, 3);
+
+        let mut empty: [i32; 0] = [];
+        assert!(!next_permutation(&mut empty));
+    }
+
+    // ---- to_base / from_base tests ----
+
+    #[test]
+    fn to_base_is_least_significant_first() {
+        assert_eq!(to_base(6, 2), vec![0, 1, 1]);
+        assert_eq!(to_base(255, 16), vec![15, 15]);
+        assert_eq!(to_base(0, 7), vec![0]);
+    }
+
+    #[test]
+    fn base_round_trips() {
+        for n in [0, 1, 2, 3, 26, 12345, 987_654_321, u64::MAX] {
+            assert_eq!(from_base(&to_base(n, 3), 3), n, "base 3: {n}");
+            assert_eq!(from_base(&to_base(n, 16), 16), n, "base 16: {n}");
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "base must be at least 2")]
+    fn to_base_rejects_base_one() {
+        to_base(5, 1);
+    }
 }
@@ -0,0 +1,29 @@
+use rayon::prelude::*;
+
+/// Count how many items satisfy `f`, evaluating `f` in parallel.
+pub fn par_map_count<T, F>(items: &[T], f: F) -> usize
+where
+    T: Send + Sync,
+    F: Fn(&T) -> bool + Sync,
+{
+    items.par_iter().filter(|item| f(item)).count()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // ---- par_map_count tests ----
+
+    #[test]
+    fn par_map_count_counts_even_numbers() {
+        let nums: Vec<u32> = (1..=1000).collect();
+        assert_eq!(par_map_count(&nums, |n| n % 2 == 0), 500);
+    }
+
+    #[test]
+    fn par_map_count_on_empty_slice() {
+        let nums: Vec<u32> = Vec::new();
+        assert_eq!(par_map_count(&nums, |_| true), 0);
+    }
+}
@@ -0,0 +1,1095 @@
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
+
+use crate::grid::{Direction, Point};
+use crate::math::crt;
+use crate::search::{bfs, solve_assignment};
+
+/// Run "<reg> inc/dec <n> if <cond_reg> <op> <value>" instructions.
+///
+/// Each instruction is (register, delta, condition register, ordering, value):
+/// `delta` is added to `register` when `cond_reg.cmp(&value) == ordering`.
+/// Write `dec n` as a delta of `-n`. Only `<`, `==` and `>` map directly onto
+/// an `Ordering`; rewrite `a >= 1` as `a > 0` and `a <= 1` as `a < 2`.
+///
+/// Registers start at 0. Returns the final registers and the highest value any
+/// register held at any point (at least 0, the starting value).
+pub fn simulate_registers(
+    instrs: &[(String, i64, String, Ordering, i64)],
+) -> (HashMap<String, i64>, i64) {
+    let mut regs: HashMap<String, i64> = HashMap::new();
+    let mut peak = 0;
+
+    for (reg, delta, cond_reg, ord, value) in instrs {
+        let cond = regs.get(cond_reg).copied().unwrap_or(0);
+        if cond.cmp(value) == *ord {
+            let r = regs.entry(reg.clone()).or_insert(0);
+            *r += delta;
+            peak = peak.max(*r);
+        }
+    }
+
+    (regs, peak)
+}
+
+/// Follow jump offsets until leaving the list, returning the number of steps.
+/// After each jump the offset just used is incremented; with `strange`, an
+/// offset of three or more is decremented instead. Mutates `offsets` in place.
+pub fn run_jumps(offsets: &mut [i64], strange: bool) -> u64 {
+    let mut pc: i64 = 0;
+    let mut steps = 0;
+
+    while let Some(off) = usize::try_from(pc).ok().and_then(|i| offsets.get_mut(i)) {
+        pc += *off;
+        if strange && *off >= 3 {
+            *off -= 1;
+        } else {
+            *off += 1;
+        }
+        steps += 1;
+    }
+
+    steps
+}
+
+/// Spinlock: starting from the buffer [0], step forward `step` places and
+/// insert the next value (1, 2, ..., `iterations`) after the current position.
+/// Returns the value that ends up right after `after`.
+///
+/// Asking for the value after 0 runs in constant memory, since 0 never moves
+/// from index 0; any other target builds the buffer. Panics if `after` is
+/// larger than `iterations`.
+pub fn spinlock_value_after(step: usize, iterations: usize, after: usize) -> usize {
+    assert!(after <= iterations, "{after} is never inserted");
+
+    if after == 0 {
+        let (mut pos, mut next_to_zero) = (0, 0);
+        for v in 1..=iterations {
+            pos = (pos + step) % v + 1;
+            if pos == 1 {
+                next_to_zero = v;
+            }
+        }
+        return next_to_zero;
+    }
+
+    let mut buf = vec![0];
+    let mut pos = 0;
+    for v in 1..=iterations {
+        pos = (pos + step) % buf.len() + 1;
+        buf.insert(pos, v);
+    }
+    let i = buf.iter().position(|&v| v == after).unwrap();
+    buf[(i + 1) % buf.len()]
+}
+
+/// One instruction of a monkey-map path.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Step {
+    Forward(u32),
+    Left,
+    Right,
+}
+
+/// Walk `path` over a board of `.` (open), `#` (wall) and ` ` (off the map),
+/// starting on the leftmost open tile of the top row facing East. Rows may be
+/// ragged. Stepping off the map calls `wrap(pos, dir)` with the last tile on
+/// the map, which returns where (and facing which way) the walker reappears;
+/// if that tile is a wall the walker stays put. Returns the final position and
+/// heading.
+pub fn walk_map(
+    grid: &[Vec<char>],
+    path: &[Step],
+    wrap: impl Fn(Point, Direction) -> (Point, Direction),
+) -> (Point, Direction) {
+    let tile = |p: Point| -> Option<char> {
+        let c = *grid.get(usize::try_from(p.y).ok()?)?.get(usize::try_from(p.x).ok()?)?;
+        (c != ' ').then_some(c)
+    };
+    let start_x = grid[0].iter().position(|&c| c == '.').expect("no open tile in top row");
+    let mut pos = Point::new(start_x as i32, 0);
+    let mut dir = Direction::East;
+
+    for &step in path {
+        match step {
+            Step::Left => dir = dir.turn_left(),
+            Step::Right => dir = dir.turn_right(),
+            Step::Forward(n) => {
+                for _ in 0..n {
+                    let d = dir.delta();
+                    let ahead = Point::new(pos.x + d.x, pos.y + d.y);
+                    let (next, next_dir) = match tile(ahead) {
+                        Some(_) => (ahead, dir),
+                        None => wrap(pos, dir),
+                    };
+                    if tile(next) != Some('.') {
+                        break;
+                    }
+                    (pos, dir) = (next, next_dir);
+                }
+            }
+        }
+    }
+    (pos, dir)
+}
+
+/// Find the one program in a tower whose weight must change so that, at every
+/// node, all child sub-towers weigh the same. Returns that program and its
+/// corrected weight, or None if the tower is already balanced. A node with
+/// exactly two differing children is ambiguous and is skipped.
+pub fn find_unbalanced(
+    weights: &HashMap<String, u64>,
+    children: &HashMap<String, Vec<String>>,
+    root: &str,
+) -> Option<(String, u64)> {
+    // Ok(total weight) if balanced, Err(answer) as soon as a fix is found
+    fn total(
+        node: &str,
+        weights: &HashMap<String, u64>,
+        children: &HashMap<String, Vec<String>>,
+    ) -> Result<u64, Option<(String, u64)>> {
+        let kids = children.get(node).map_or(&[][..], |v| v.as_slice());
+        let totals = kids
+            .iter()
+            .map(|k| total(k, weights, children))
+            .collect::<Result<Vec<u64>, _>>()?;
+
+        let count = |t: u64| totals.iter().filter(|&&x| x == t).count();
+        if let Some(i) = totals.iter().position(|&t| count(t) == 1)
+            && let Some(&common) = totals.iter().find(|&&t| count(t) > 1)
+        {
+            let fixed = weights[&kids[i]] + common - totals[i];
+            return Err(Some((kids[i].clone(), fixed)));
+        }
+        Ok(weights[node] + totals.iter().sum::<u64>())
+    }
+
+    total(root, weights, children).err().flatten()
+}
+
+/// Sum of the digits that equal the digit `offset` positions further along,
+/// treating the sequence as circular (inverse captcha).
+pub fn captcha_sum(digits: &[u32], offset: usize) -> u32 {
+    let n = digits.len();
+    (0..n)
+        .filter(|&i| digits[i] == digits[(i + offset) % n])
+        .map(|i| digits[i])
+        .sum()
+}
+
+/// Spreadsheet checksum: sum over rows of (max - min). Empty rows count 0.
+pub fn row_range_sum(rows: &[Vec<i64>]) -> i64 {
+    rows.iter()
+        .filter_map(|r| Some(r.iter().max()? - r.iter().min()?))
+        .sum()
+}
+
+/// Sum over rows of `a / b` for the (first) pair where `b` evenly divides `a`.
+/// Rows without such a pair count 0; zeros are never used as divisors.
+pub fn row_evendiv_sum(rows: &[Vec<i64>]) -> i64 {
+    rows.iter()
+        .filter_map(|r| {
+            r.iter().enumerate().find_map(|(i, &a)| {
+                r.iter()
+                    .enumerate()
+                    .find(|&(j, &b)| i != j && b != 0 && a % b == 0)
+                    .map(|(_, &b)| a / b)
+            })
+        })
+        .sum()
+}
+
+/// True if `value` lies in any of the inclusive `ranges`.
+pub fn valid_value(value: i64, ranges: &[(i64, i64)]) -> bool {
+    ranges.iter().any(|&(lo, hi)| (lo..=hi).contains(&value))
+}
+
+/// Work out which ticket column each named field is. Tickets with a value no
+/// field accepts are discarded first; a field may only take a column whose
+/// values all satisfy its ranges. Returns field name -> column index, or None
+/// if `solve_assignment` can't pin it down.
+pub fn assign_ticket_fields(
+    fields: &[(String, Vec<(i64, i64)>)],
+    tickets: &[Vec<i64>],
+) -> Option<HashMap<String, usize>> {
+    let valid: Vec<&Vec<i64>> = tickets
+        .iter()
+        .filter(|t| t.iter().all(|&v| fields.iter().any(|(_, r)| valid_value(v, r))))
+        .collect();
+    let columns = valid.first().map_or(0, |t| t.len());
+
+    let candidates: HashMap<String, HashSet<usize>> = fields
+        .iter()
+        .map(|(name, ranges)| {
+            let cols = (0..columns)
+                .filter(|&c| valid.iter().all(|t| valid_value(t[c], ranges)))
+                .collect();
+            (name.clone(), cols)
+        })
+        .collect();
+
+    solve_assignment(&candidates)
+}
+
+/// Decode a boarding pass like `FBFBBFFRLR` as a binary number (`F`/`L` are
+/// 0, `B`/`R` are 1), which is row * 8 + column. Other characters are ignored.
+pub fn seat_id(s: &str) -> u32 {
+    s.chars().fold(0, |id, c| match c {
+        'F' | 'L' => id << 1,
+        'B' | 'R' => (id << 1) | 1,
+        _ => id,
+    })
+}
+
+/// The single ID missing from `ids` whose neighbours on both sides are present.
+pub fn find_missing_seat(ids: &[u32]) -> Option<u32> {
+    let mut sorted = ids.to_vec();
+    sorted.sort_unstable();
+    sorted.windows(2).find(|w| w[1] == w[0] + 2).map(|w| w[0] + 1)
+}
+
+/// Number of distinct answers given by anyone in a group (one person per line).
+pub fn group_any_count(group: &str) -> usize {
+    group.lines().flat_map(|l| l.trim().chars()).collect::<HashSet<char>>().len()
+}
+
+/// Number of answers given by everyone in a group (one person per line).
+/// Blank lines are ignored; a group with no people counts 0.
+pub fn group_all_count(group: &str) -> usize {
+    group
+        .lines()
+        .map(str::trim)
+        .filter(|l| !l.is_empty())
+        .map(|l| l.chars().collect::<HashSet<char>>())
+        .reduce(|a, b| &a & &b)
+        .map_or(0, |s| s.len())
+}
+
+/// How many bag colours can eventually contain a `target` bag, given
+/// colour -> [(count, inner colour)] rules.
+pub fn count_containers(graph: &HashMap<String, Vec<(usize, String)>>, target: &str) -> usize {
+    let mut parents: HashMap<&str, Vec<&str>> = HashMap::new();
+    for (outer, inner) in graph {
+        for (_, colour) in inner {
+            parents.entry(colour.as_str()).or_default().push(outer.as_str());
+        }
+    }
+    let reachable = bfs(target, |c| parents.get(c).cloned().unwrap_or_default());
+    reachable.len() - 1
+}
+
+/// Total number of bags inside one `target` bag, given colour ->
+/// [(count, inner colour)] rules. The rules must not be cyclic.
+pub fn count_contained(graph: &HashMap<String, Vec<(usize, String)>>, target: &str) -> usize {
+    graph.get(target).map_or(0, |inner| {
+        inner.iter().map(|(n, colour)| n * (1 + count_contained(graph, colour))).sum()
+    })
+}
+
+/// Count the 1-jolt and 3-jolt gaps in the full adapter chain, including the
+/// outlet (0) and the device (3 above the largest adapter).
+pub fn jolt_differences(adapters: &[u64]) -> (usize, usize) {
+    let mut chain = adapters.to_vec();
+    chain.push(0);
+    chain.sort_unstable();
+    chain.push(chain.last().unwrap() + 3);
+
+    let gaps = |d: u64| chain.windows(2).filter(|w| w[1] - w[0] == d).count();
+    (gaps(1), gaps(3))
+}
+
+/// Number of distinct adapter subsets connecting the outlet (0) to the device,
+/// where each step may rise by 1 to 3 jolts.
+pub fn adapter_arrangements(adapters: &[u64]) -> u64 {
+    let mut sorted = adapters.to_vec();
+    sorted.sort_unstable();
+
+    // ways[j] = ways to reach a joltage of j
+    let mut ways: HashMap<u64, u64> = HashMap::from([(0, 1)]);
+    for &a in &sorted {
+        let n = (a.saturating_sub(3)..a).filter_map(|j| ways.get(&j)).sum();
+        ways.insert(a, n);
+    }
+    // the device is only reachable from the largest adapter
+    sorted.last().map_or(1, |last| ways[last])
+}
+
+/// First number (after the preamble) that is not the sum of two different
+/// entries among the `window` numbers before it.
+pub fn first_invalid(nums: &[i64], window: usize) -> Option<i64> {
+    nums.windows(window + 1)
+        .find(|w| {
+            let (prev, target) = (&w[..window], w[window]);
+            !prev.iter().enumerate().any(|(i, &a)| prev[i + 1..].contains(&(target - a)))
+        })
+        .map(|w| w[window])
+}
+
+/// Inclusive index range `(start, end)` of at least two contiguous numbers
+/// summing to `target`. Uses a sliding window, so `nums` must be non-negative.
+pub fn contiguous_sum_range(nums: &[i64], target: i64) -> Option<(usize, usize)> {
+    let (mut start, mut sum) = (0, 0);
+    for (end, &n) in nums.iter().enumerate() {
+        sum += n;
+        while sum > target && start < end {
+            sum -= nums[start];
+            start += 1;
+        }
+        if sum == target && end > start {
+            return Some((start, end));
+        }
+    }
+    None
+}
+
+/// One round of the ferry seating automaton over `L` (empty), `#` (occupied)
+/// and `.` (floor). A seat's neighbours are the 8 adjacent cells, or with
+/// `los` the first seat visible in each of the 8 directions. Empty seats with
+/// no occupied neighbours fill; occupied seats with at least
+/// `crowd_threshold` occupied neighbours empty.
+pub fn seat_step(grid: &[Vec<char>], los: bool, crowd_threshold: usize) -> Vec<Vec<char>> {
+    let at = |p: Point| -> Option<char> {
+        grid.get(usize::try_from(p.y).ok()?)?.get(usize::try_from(p.x).ok()?).copied()
+    };
+    let occupied_around = |p: Point| {
+        Point::new(0, 0)
+            .neighbors8()
+            .into_iter()
+            .filter(|&d| {
+                let mut q = p + d;
+                while los && at(q) == Some('.') {
+                    q = q + d;
+                }
+                at(q) == Some('#')
+            })
+            .count()
+    };
+
+    (0..grid.len())
+        .map(|y| {
+            (0..grid[y].len())
+                .map(|x| {
+                    let p = Point::new(x as i32, y as i32);
+                    match grid[y][x] {
+                        'L' if occupied_around(p) == 0 => '#',
+                        '#' if occupied_around(p) >= crowd_threshold => 'L',
+                        c => c,
+                    }
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// Apply `seat_step` until the seating stops changing and return it.
+pub fn seat_fixpoint(grid: &[Vec<char>], los: bool, crowd_threshold: usize) -> Vec<Vec<char>> {
+    let mut cur = grid.to_vec();
+    loop {
+        let next = seat_step(&cur, los, crowd_threshold);
+        if next == cur {
+            return cur;
+        }
+        cur = next;
+    }
+}
+
+/// Follow rain-risk navigation instructions (`N/S/E/W` n, `L/R` degrees in
+/// multiples of 90, `F` n) and return the ship's Manhattan distance from the
+/// start. With `waypoint: None` the ship starts facing East and `N/S/E/W` move
+/// the ship; with `Some(wp)` (relative to the ship, y down so North is -y)
+/// they move the waypoint, and `L/R` rotate it instead of the ship.
+pub fn navigate(instrs: &[(char, i32)], waypoint: Option<Point>) -> i64 {
+    let mut ship = Point::new(0, 0);
+    let mut vector = waypoint.unwrap_or(Point::new(1, 0));
+
+    for &(op, n) in instrs {
+        let shift = match op {
+            'N' => Point::new(0, -n),
+            'S' => Point::new(0, n),
+            'E' => Point::new(n, 0),
+            'W' => Point::new(-n, 0),
+            'L' | 'R' => {
+                let turns = (n / 90).rem_euclid(4);
+                for _ in 0..turns {
+                    vector = if op == 'R' { vector.turn_cw() } else { vector.turn_ccw() };
+                }
+                continue;
+            }
+            'F' => {
+                ship = ship + vector.scale(n);
+                continue;
+            }
+            _ => panic!("unknown navigation instruction {op:?}"),
+        };
+        if waypoint.is_some() {
+            vector = vector + shift;
+        } else {
+            ship = ship + shift;
+        }
+    }
+
+    ship.manhattan(Point::new(0, 0)) as i64
+}
+
+/// The first bus (by ID, which is also its period) leaving at or after
+/// `depart`, as `(id, minutes to wait)`. Panics if `buses` is empty.
+pub fn earliest_bus(depart: i64, buses: &[i64]) -> (i64, i64) {
+    buses
+        .iter()
+        .map(|&id| (id, (-depart).rem_euclid(id)))
+        .min_by_key(|&(_, wait)| wait)
+        .expect("no buses")
+}
+
+/// Earliest timestamp t such that each bus in `(offset, id)` departs at
+/// t + offset. Bus IDs must be pairwise coprime (they are primes in practice).
+pub fn align_buses(buses: &[(i64, i64)]) -> i64 {
+    let congruences: Vec<(i64, i64)> = buses.iter().map(|&(offset, id)| (-offset, id)).collect();
+    crt(&congruences).expect("bus IDs are not pairwise coprime").0
+}
+
+/// Docking-data v1: overwrite `value`'s bits where `mask` (most significant
+/// bit first) has `0` or `1`; `X` leaves the bit unchanged.
+pub fn apply_mask_value(mask: &str, value: u64) -> u64 {
+    mask.chars().rev().enumerate().fold(value, |v, (bit, c)| match c {
+        '0' => v & !(1 << bit),
+        '1' => v | (1 << bit),
+        _ => v,
+    })
+}
+
+/// Docking-data v2: set `addr`'s bits where `mask` has `1`, keep them for `0`,
+/// and let `X` bits float, giving every combination (2^count(X) addresses).
+pub fn apply_mask_address(mask: &str, addr: u64) -> Vec<u64> {
+    mask.chars().rev().enumerate().fold(vec![addr], |addrs, (bit, c)| match c {
+        '1' => addrs.into_iter().map(|a| a | (1 << bit)).collect(),
+        'X' => addrs
+            .into_iter()
+            .flat_map(|a| [a & !(1 << bit), a | (1 << bit)])
+            .collect(),
+        _ => addrs,
+    })
+}
+
+/// Where a pattern mirrors: `Row(n)` means the line lies between rows `n - 1`
+/// and `n` (so `n` rows are above it), `Column(n)` likewise for columns.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum Reflection {
+    Row(usize),
+    Column(usize),
+}
+
+/// First horizontal, then vertical mirror line whose two halves differ in
+/// exactly `smudges` cells (0 for a perfect reflection).
+pub fn find_reflection(grid: &[Vec<char>], smudges: usize) -> Option<Reflection> {
+    let h = grid.len();
+    let w = grid.first().map_or(0, |r| r.len());
+
+    let row_diff = |split: usize| -> usize {
+        (0..split.min(h - split))
+            .map(|d| {
+                let (a, b) = (&grid[split - 1 - d], &grid[split + d]);
+                a.iter().zip(b).filter(|(x, y)| x != y).count()
+            })
+            .sum()
+    };
+    let col_diff = |split: usize| -> usize {
+        grid.iter()
+            .map(|row| {
+                (0..split.min(w - split))
+                    .filter(|&d| row[split - 1 - d] != row[split + d])
+                    .count()
+            })
+            .sum()
+    };
+
+    (1..h)
+        .find(|&s| row_diff(s) == smudges)
+        .map(Reflection::Row)
+        .or_else(|| (1..w).find(|&s| col_diff(s) == smudges).map(Reflection::Column))
+}
+
+/// Score of the grid after `target` applications of `step`, skipping ahead
+/// once a previously seen state repeats (e.g. spin cycles in tilt puzzles).
+/// The states are expected to cycle early: every state up to the repeat is
+/// stored, so a `target` near u64::MAX without a cycle would never finish.
+pub fn cycle_load(
+    initial: Vec<Vec<char>>,
+    mut step: impl FnMut(&Vec<Vec<char>>) -> Vec<Vec<char>>,
+    target: u64,
+    score: impl Fn(&Vec<Vec<char>>) -> u64,
+) -> u64 {
+    let mut seen: HashMap<Vec<Vec<char>>, u64> = HashMap::new();
+    let mut states: Vec<Vec<Vec<char>>> = Vec::new();
+    let mut cur = initial;
+
+    for i in 0..=target {
+        if let Some(&mu) = seen.get(&cur) {
+            let lambda = i - mu;
+            let idx = mu + (target - mu) % lambda;
+            return score(&states[idx as usize]);
+        }
+        let next = step(&cur);
+        seen.insert(cur.clone(), i);
+        states.push(cur);
+        cur = next;
+    }
+
+    score(&states[target as usize])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // ---- simulate_registers tests ----
+
+    #[test]
+    fn simulate_registers_matches_example() {
+        // b inc 5 if a > 1
+        // a inc 1 if b < 5
+        // c dec -10 if a >= 1
+        // c inc -20 if c == 10
+        let instrs = vec![
+            ("b".to_string(), 5, "a".to_string(), Ordering::Greater, 1),
+            ("a".to_string(), 1, "b".to_string(), Ordering::Less, 5),
+            ("c".to_string(), 10, "a".to_string(), Ordering::Greater, 0),
+            ("c".to_string(), -20, "c".to_string(), Ordering::Equal, 10),
+        ];
+
+        let (regs, peak) = simulate_registers(&instrs);
+
+        assert_eq!(regs.values().copied().max(), Some(1));
+        assert_eq!(regs.get("a"), Some(&1));
+        assert_eq!(regs.get("c"), Some(&-10));
+        assert_eq!(regs.get("b"), None); // never touched
+        assert_eq!(peak, 10);
+    }
+
+    // ---- run_jumps tests ----
+
+    #[test]
+    fn run_jumps_matches_example() {
+        let mut offsets = vec![0, 3, 0, 1, -3];
+        assert_eq!(run_jumps(&mut offsets, false), 5);
+        assert_eq!(offsets, vec![2, 5, 0, 1, -2]);
+    }
+
+    #[test]
+    fn run_jumps_strange_rule_matches_example() {
+        let mut offsets = vec![0, 3, 0, 1, -3];
+        assert_eq!(run_jumps(&mut offsets, true), 10);
+        assert_eq!(offsets, vec![2, 3, 2, 3, -1]);
+    }
+
+    #[test]
+    fn run_jumps_exits_backwards() {
+        assert_eq!(run_jumps(&mut [-1], false), 1);
+        assert_eq!(run_jumps(&mut [], false), 0);
+    }
+
+    // ---- spinlock_value_after tests ----
+
+    #[test]
+    fn spinlock_value_after_matches_example() {
+        // after 9 insertions with step 3: 0 (9) 5 7 2 4 3 8 6 1
+        assert_eq!(spinlock_value_after(3, 9, 5), 7);
+        assert_eq!(spinlock_value_after(3, 9, 1), 0); // wraps around
+        assert_eq!(spinlock_value_after(3, 2017, 2017), 638);
+    }
+
+    #[test]
+    fn spinlock_value_after_zero_without_buffer() {
+        assert_eq!(spinlock_value_after(3, 9, 0), 9);
+        // agrees with the materialized buffer
+        for n in 1..50 {
+            let mut buf = vec![0];
+            let mut pos = 0;
+            for v in 1..=n {
+                pos = (pos + 3) % buf.len() + 1;
+                buf.insert(pos, v);
+            }
+            assert_eq!(spinlock_value_after(3, n, 0), buf[1], "iterations {n}");
+        }
+    }
+
+    // ---- walk_map tests ----
+
+    const MONKEY_MAP: &str = "        ...#
+        .#..
+        #...
+        ....
+...#.......#
+........#...
+..#....#....
+..........#.
+        ...#....
+        .....#..
+        .#......
+        ......#.";
+
+    #[test]
+    fn walk_map_flat_wrap_matches_example_password() {
+        let grid: Vec<Vec<char>> = MONKEY_MAP.lines().map(|l| l.chars().collect()).collect();
+        let path = [
+            Step::Forward(10),
+            Step::Right,
+            Step::Forward(5),
+            Step::Left,
+            Step::Forward(5),
+            Step::Right,
+            Step::Forward(10),
+            Step::Left,
+            Step::Forward(4),
+            Step::Right,
+            Step::Forward(5),
+            Step::Left,
+            Step::Forward(5),
+        ];
+        let on_map = |p: Point| {
+            p.x >= 0
+                && p.y >= 0
+                && grid
+                    .get(p.y as usize)
+                    .and_then(|r| r.get(p.x as usize))
+                    .is_some_and(|&c| c != ' ')
+        };
+        // flat wrap: walk backwards to the opposite edge of the row/column
+        let wrap = |mut p: Point, dir: Direction| {
+            let back = dir.turn_right().turn_right().delta();
+            while on_map(Point::new(p.x + back.x, p.y + back.y)) {
+                p = Point::new(p.x + back.x, p.y + back.y);
+            }
+            (p, dir)
+        };
+
+        let (pos, dir) = walk_map(&grid, &path, wrap);
+        let facing = match dir {
+            Direction::East => 0,
+            Direction::South => 1,
+            Direction::West => 2,
+            Direction::North => 3,
+        };
+        assert_eq!(1000 * (pos.y + 1) + 4 * (pos.x + 1) + facing, 6032);
+    }
+
+    // ---- find_unbalanced tests ----
+
+    #[test]
+    fn find_unbalanced_on_example_tower() {
+        let spec = "pbga (66)
+xhth (57)
+ebii (61)
+havc (66)
+ktlj (57)
+fwft (72) -> ktlj, cntj, xhth
+qoyq (66)
+padx (45) -> pbga, havc, qoyq
+tknk (41) -> ugml, padx, fwft
+jptl (61)
+ugml (68) -> gyxo, ebii, jptl
+gyxo (61)
+cntj (57)";
+        let mut weights = HashMap::new();
+        let mut children = HashMap::new();
+        for line in spec.lines() {
+            let (head, kids) = line.split_once(" -> ").unwrap_or((line, ""));
+            let (name, w) = head.split_once(" (").unwrap();
+            weights.insert(name.to_string(), w.trim_end_matches(')').parse().unwrap());
+            if !kids.is_empty() {
+                children.insert(name.to_string(), kids.split(", ").map(String::from).collect());
+            }
+        }
+
+        assert_eq!(find_unbalanced(&weights, &children, "tknk"), Some(("ugml".to_string(), 60)));
+        assert_eq!(find_unbalanced(&weights, &children, "padx"), None);
+    }
+
+    // ---- captcha_sum tests ----
+
+    fn captcha_digits(s: &str) -> Vec<u32> {
+        s.chars().map(|c| c.to_digit(10).unwrap()).collect()
+    }
+
+    #[test]
+    fn captcha_sum_next_digit() {
+        for (s, want) in [("1122", 3), ("1111", 4), ("1234", 0), ("91212129", 9)] {
+            assert_eq!(captcha_sum(&captcha_digits(s), 1), want, "{s}");
+        }
+    }
+
+    #[test]
+    fn captcha_sum_halfway_around() {
+        for (s, want) in [("1212", 6), ("1221", 0), ("123425", 4), ("123123", 12), ("12131415", 4)] {
+            let d = captcha_digits(s);
+            assert_eq!(captcha_sum(&d, d.len() / 2), want, "{s}");
+        }
+    }
+
+    // ---- row checksum tests ----
+
+    #[test]
+    fn row_range_sum_matches_example() {
+        let rows = vec![vec![5, 1, 9, 5], vec![7, 5, 3], vec![2, 4, 6, 8]];
+        assert_eq!(row_range_sum(&rows), 18);
+        assert_eq!(row_range_sum(&[vec![]]), 0);
+    }
+
+    #[test]
+    fn row_evendiv_sum_matches_example() {
+        let rows = vec![vec![5, 9, 2, 8], vec![9, 4, 7, 3], vec![3, 8, 6, 5]];
+        assert_eq!(row_evendiv_sum(&rows), 9);
+    }
+
+    // ---- ticket field tests ----
+
+    fn ticket_fields(spec: &[(&str, [(i64, i64); 2])]) -> Vec<(String, Vec<(i64, i64)>)> {
+        spec.iter().map(|(n, r)| (n.to_string(), r.to_vec())).collect()
+    }
+
+    #[test]
+    fn valid_value_error_rate_matches_example() {
+        let fields = ticket_fields(&[
+            ("class", [(1, 3), (5, 7)]),
+            ("row", [(6, 11), (33, 44)]),
+            ("seat", [(13, 40), (45, 50)]),
+        ]);
+        let nearby = [vec![7, 3, 47], vec![40, 4, 50], vec![55, 2, 20], vec![38, 6, 12]];
+        let error_rate: i64 = nearby
+            .iter()
+            .flatten()
+            .filter(|&&v| !fields.iter().any(|(_, r)| valid_value(v, r)))
+            .sum();
+        assert_eq!(error_rate, 71);
+    }
+
+    #[test]
+    fn assign_ticket_fields_matches_example() {
+        let fields = ticket_fields(&[
+            ("class", [(0, 1), (4, 19)]),
+            ("row", [(0, 5), (8, 19)]),
+            ("seat", [(0, 13), (16, 19)]),
+        ]);
+        let nearby = [vec![3, 9, 18], vec![15, 1, 5], vec![5, 14, 9], vec![20, 0, 0]];
+        let expected = HashMap::from([
+            ("row".to_string(), 0),
+            ("class".to_string(), 1),
+            ("seat".to_string(), 2),
+        ]);
+        assert_eq!(assign_ticket_fields(&fields, &nearby), Some(expected));
+    }
+
+    // ---- seat_id tests ----
+
+    #[test]
+    fn seat_id_matches_examples() {
+        for (pass, id) in [("FBFBBFFRLR", 357), ("BFFFBBFRRR", 567), ("FFFBBBFRRR", 119), ("BBFFBBFRLL", 820)] {
+            assert_eq!(seat_id(pass), id, "{pass}");
+        }
+    }
+
+    #[test]
+    fn find_missing_seat_finds_gap() {
+        assert_eq!(find_missing_seat(&[12, 9, 8, 11, 13]), Some(10));
+        assert_eq!(find_missing_seat(&[3, 4, 5]), None);
+    }
+
+    // ---- customs group tests ----
+
+    #[test]
+    fn group_counts_on_example() {
+        let input = "abc\n\na\nb\nc\n\nab\nac\n\na\na\na\na\n\nb\n";
+        let groups = crate::input::groups(input);
+        let any: usize = groups.iter().map(|g| group_any_count(g)).sum();
+        let all: usize = groups.iter().map(|g| group_all_count(g)).sum();
+        assert_eq!((any, all), (11, 6));
+        assert_eq!(group_all_count("ab\nac"), 1);
+    }
+
+    // ---- bag containment tests ----
+
+    fn bag_rules(spec: &str) -> HashMap<String, Vec<(usize, String)>> {
+        spec.lines()
+            .map(|line| {
+                let (outer, rest) = line.split_once(" bags contain ").unwrap();
+                let inner = rest
+                    .trim_end_matches('.')
+                    .split(", ")
+                    .filter(|part| *part != "no other bags")
+                    .map(|part| {
+                        let (n, colour) = part.split_once(' ').unwrap();
+                        let colour = colour.trim_end_matches(" bags").trim_end_matches(" bag");
+                        (n.parse().unwrap(), colour.to_string())
+                    })
+                    .collect();
+                (outer.to_string(), inner)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn bag_counts_match_first_example() {
+        let graph = bag_rules(
+            "light red bags contain 1 bright white bag, 2 muted yellow bags.
+dark orange bags contain 3 bright white bags, 4 muted yellow bags.
+bright white bags contain 1 shiny gold bag.
+muted yellow bags contain 2 shiny gold bags, 9 faded blue bags.
+shiny gold bags contain 1 dark olive bag, 2 vibrant plum bags.
+dark olive bags contain 3 faded blue bags, 4 dotted black bags.
+vibrant plum bags contain 5 faded blue bags, 6 dotted black bags.
+faded blue bags contain no other bags.
+dotted black bags contain no other bags.",
+        );
+        assert_eq!(count_containers(&graph, "shiny gold"), 4);
+        assert_eq!(count_contained(&graph, "shiny gold"), 32);
+    }
+
+    #[test]
+    fn count_contained_matches_second_example() {
+        let graph = bag_rules(
+            "shiny gold bags contain 2 dark red bags.
+dark red bags contain 2 dark orange bags.
+dark orange bags contain 2 dark yellow bags.
+dark yellow bags contain 2 dark green bags.
+dark green bags contain 2 dark blue bags.
+dark blue bags contain 2 dark violet bags.
+dark violet bags contain no other bags.",
+        );
+        assert_eq!(count_contained(&graph, "shiny gold"), 126);
+        assert_eq!(count_containers(&graph, "dark violet"), 6);
+    }
+
+    // ---- adapter tests ----
+
+    const SMALL_ADAPTERS: [u64; 11] = [16, 10, 15, 5, 1, 11, 7, 19, 6, 12, 4];
+    const LARGE_ADAPTERS: [u64; 31] = [
+        28, 33, 18, 42, 31, 14, 46, 20, 48, 47, 24, 23, 49, 45, 19, 38, 39, 11, 1, 32, 25, 35, 8,
+        17, 7, 9, 4, 2, 34, 10, 3,
+    ];
+
+    #[test]
+    fn jolt_differences_match_examples() {
+        assert_eq!(jolt_differences(&SMALL_ADAPTERS), (7, 5));
+        let (ones, threes) = jolt_differences(&LARGE_ADAPTERS);
+        assert_eq!(ones * threes, 22 * 10);
+    }
+
+    #[test]
+    fn adapter_arrangements_match_examples() {
+        assert_eq!(adapter_arrangements(&SMALL_ADAPTERS), 8);
+        assert_eq!(adapter_arrangements(&LARGE_ADAPTERS), 19208);
+    }
+
+    // ---- XMAS cipher tests ----
+
+    const XMAS: [i64; 20] = [
+        35, 20, 15, 25, 47, 40, 62, 55, 65, 95, 102, 117, 150, 182, 127, 219, 299, 277, 309, 576,
+    ];
+
+    #[test]
+    fn first_invalid_matches_example() {
+        assert_eq!(first_invalid(&XMAS, 5), Some(127));
+        assert_eq!(first_invalid(&[1, 2, 3], 2), None);
+    }
+
+    #[test]
+    fn contiguous_sum_range_finds_weakness() {
+        let (a, b) = contiguous_sum_range(&XMAS, 127).unwrap();
+        assert_eq!((a, b), (2, 5));
+        let range = &XMAS[a..=b];
+        assert_eq!(range.iter().min().unwrap() + range.iter().max().unwrap(), 62);
+        assert_eq!(contiguous_sum_range(&[5, 1], 5), None);
+    }
+
+    // ---- seat_step tests ----
+
+    const SEATS: &str = "L.LL.LL.LL
+LLLLLLL.LL
+L.L.L..L..
+LLLL.LL.LL
+L.LL.LL.LL
+L.LLLLL.LL
+..L.L.....
+LLLLLLLLLL
+L.LLLLLL.L
+L.LLLLL.LL";
+
+    fn occupied(grid: &[Vec<char>]) -> usize {
+        grid.iter().flatten().filter(|&&c| c == '#').count()
+    }
+
+    #[test]
+    fn seat_fixpoint_adjacent_rule() {
+        let grid: Vec<Vec<char>> = SEATS.lines().map(|l| l.chars().collect()).collect();
+        let first = seat_step(&grid, false, 4);
+        assert_eq!(occupied(&first), 71); // every seat fills in round one
+        assert_eq!(occupied(&seat_fixpoint(&grid, false, 4)), 37);
+    }
+
+    #[test]
+    fn seat_fixpoint_line_of_sight_rule() {
+        let grid: Vec<Vec<char>> = SEATS.lines().map(|l| l.chars().collect()).collect();
+        assert_eq!(occupied(&seat_fixpoint(&grid, true, 5)), 26);
+    }
+
+    // ---- navigate tests ----
+
+    const NAV: [(char, i32); 5] = [('F', 10), ('N', 3), ('F', 7), ('R', 90), ('F', 11)];
+
+    #[test]
+    fn navigate_moves_ship_directly() {
+        assert_eq!(navigate(&NAV, None), 25);
+        assert_eq!(navigate(&[('L', 270), ('F', 2)], None), 2);
+    }
+
+    #[test]
+    fn navigate_with_waypoint() {
+        assert_eq!(navigate(&NAV, Some(Point::new(10, -1))), 286);
+    }
+
+    // ---- bus schedule tests ----
+
+    fn bus_offsets(schedule: &str) -> Vec<(i64, i64)> {
+        schedule
+            .split(',')
+            .enumerate()
+            .filter_map(|(i, id)| Some((i as i64, id.parse().ok()?)))
+            .collect()
+    }
+
+    #[test]
+    fn earliest_bus_matches_example() {
+        let (id, wait) = earliest_bus(939, &[7, 13, 59, 31, 19]);
+        assert_eq!((id, wait), (59, 5));
+        assert_eq!(earliest_bus(14, &[7, 5]), (7, 0));
+    }
+
+    #[test]
+    fn align_buses_matches_examples() {
+        assert_eq!(align_buses(&bus_offsets("7,13,x,x,59,x,31,19")), 1068781);
+        assert_eq!(align_buses(&bus_offsets("17,x,13,19")), 3417);
+        assert_eq!(align_buses(&bus_offsets("1789,37,47,1889")), 1202161486);
+    }
+
+    // ---- docking mask tests ----
+
+    fn run_docking(program: &str, v2: bool) -> u64 {
+        let mut mask = "";
+        let mut mem: HashMap<u64, u64> = HashMap::new();
+        for line in program.lines() {
+            let (lhs, rhs) = line.split_once(" = ").unwrap();
+            if lhs == "mask" {
+                mask = rhs;
+                continue;
+            }
+            let addr: u64 = lhs.trim_start_matches("mem[").trim_end_matches(']').parse().unwrap();
+            let value: u64 = rhs.parse().unwrap();
+            if v2 {
+                for a in apply_mask_address(mask, addr) {
+                    mem.insert(a, value);
+                }
+            } else {
+                mem.insert(addr, apply_mask_value(mask, value));
+            }
+        }
+        mem.values().sum()
+    }
+
+    #[test]
+    fn apply_mask_value_matches_example() {
+        let mask = "XXXXXXXXXXXXXXXXXXXXXXXXXXXXX1XXXX0X";
+        assert_eq!(apply_mask_value(mask, 11), 73);
+        assert_eq!(apply_mask_value(mask, 0), 64);
+        let program = format!("mask = {mask}\nmem[8] = 11\nmem[7] = 101\nmem[8] = 0");
+        assert_eq!(run_docking(&program, false), 165);
+    }
+
+    #[test]
+    fn apply_mask_address_matches_example() {
+        let mut addrs = apply_mask_address("000000000000000000000000000000X1001X", 42);
+        addrs.sort();
+        assert_eq!(addrs, vec![26, 27, 58, 59]);
+        let program = "mask = 000000000000000000000000000000X1001X
+mem[42] = 100
+mask = 00000000000000000000000000000000X0XX
+mem[26] = 1";
+        assert_eq!(run_docking(program, true), 208);
+    }
+
+    // ---- find_reflection tests ----
+
+    fn mirror_pattern(s: &str) -> Vec<Vec<char>> {
+        s.lines().map(|l| l.chars().collect()).collect()
+    }
+
+    const MIRROR_A: &str = "\
+#.##..##.
+..#.##.#.
+##......#
+##......#
+..#.##.#.
+..##..##.
+#.#.##.#.";
+
+    const MIRROR_B: &str = "\
+#...##..#
+#....#..#
+..##..###
+#####.##.
+#####.##.
+..##..###
+#....#..#";
+
+    #[test]
+    fn find_reflection_exact() {
+        assert_eq!(find_reflection(&mirror_pattern(MIRROR_A), 0), Some(Reflection::Column(5)));
+        assert_eq!(find_reflection(&mirror_pattern(MIRROR_B), 0), Some(Reflection::Row(4)));
+    }
+
+    #[test]
+    fn find_reflection_with_one_smudge() {
+        assert_eq!(find_reflection(&mirror_pattern(MIRROR_A), 1), Some(Reflection::Row(3)));
+        assert_eq!(find_reflection(&mirror_pattern(MIRROR_B), 1), Some(Reflection::Row(1)));
+    }
+
+    #[test]
+    fn find_reflection_none() {
+        assert_eq!(find_reflection(&mirror_pattern("#.\n.."), 0), None);
+        assert_eq!(find_reflection(&[], 0), None);
+    }
+
+    // ---- cycle_load tests ----
+
+    #[test]
+    fn cycle_load_skips_ahead_on_repeat() {
+        // a single '#' moving right along a 1x3 row, wrapping: period 3
+        let initial = vec![vec!['#', '.', '.']];
+        let step = |g: &Vec<Vec<char>>| rotate_row(g);
+        let score = |g: &Vec<Vec<char>>| g[0].iter().position(|&c| c == '#').unwrap() as u64;
+
+        assert_eq!(cycle_load(initial.clone(), step, 0, score), 0);
+        assert_eq!(cycle_load(initial.clone(), step, 2, score), 2);
+        assert_eq!(cycle_load(initial.clone(), step, 1_000_000_000, score), 1_000_000_000 % 3);
+    }
+
+    #[test]
+    fn cycle_load_with_tail_before_cycle() {
+        // counter in the grid: 0 -> 1 -> 2 -> 3 -> 4 -> 2 ... (tail 2, period 3)
+        let as_grid = |n: u32| vec![vec![char::from_digit(n, 10).unwrap()]];
+        let step = |g: &Vec<Vec<char>>| {
+            let n = g[0][0].to_digit(10).unwrap();
+            as_grid(if n == 4 { 2 } else { n + 1 })
+        };
+        let score = |g: &Vec<Vec<char>>| g[0][0].to_digit(10).unwrap() as u64;
+
+        assert_eq!(cycle_load(as_grid(0), step, 1, score), 1);
+        assert_eq!(cycle_load(as_grid(0), step, 5, score), 2);
+        assert_eq!(cycle_load(as_grid(0), step, 1000, score), [2, 3, 4][(1000 - 2) % 3]);
+    }
+
+    fn rotate_row(g: &[Vec<char>]) -> Vec<Vec<char>> {
+        let mut row = g[0].clone();
+        row.rotate_right(1);
+        vec![row]
+    }
+}
@@ -1,5 +1,8 @@
-use std::collections::{HashMap, HashSet, VecDeque};
+use std::cmp::Ordering;
+use std::collections::hash_map::Entry;
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
 use std::hash::Hash;
+use std::ops::ControlFlow;
 use priority_queue::PriorityQueue;
 
 /// Breadth-first search: returns distance map from `start`.
@@ -28,6 +31,96 @@ where
     dist
 }
 
+/// Breadth-first search from several sources at once: returns the distance
+/// from each reachable node to its nearest source. Every start has distance 0.
+pub fn bfs_multi<T, F, I>(starts: impl IntoIterator<Item = T>, mut neighbors: F) -> HashMap<T, usize>
+where
+    T: Eq + Hash + Copy,
+    F: FnMut(T) -> I,
+    I: IntoIterator<Item = T>,
+{
+    let mut dist = HashMap::new();
+    let mut q = VecDeque::new();
+
+    for s in starts {
+        if dist.insert(s, 0).is_none() {
+            q.push_back(s);
+        }
+    }
+
+    while let Some(cur) = q.pop_front() {
+        let d = dist[&cur];
+        for nb in neighbors(cur) {
+            if let Entry::Vacant(e) = dist.entry(nb) {
+                e.insert(d + 1);
+                q.push_back(nb);
+            }
+        }
+    }
+
+    dist
+}
+
+/// Breadth-first search that reports each node with its distance, in the
+/// order they are dequeued, instead of building a distance map. Returning
+/// `ControlFlow::Break` from `on_visit` stops the search; the node it broke on
+/// is returned.
+pub fn bfs_visit<T, F, I, S>(start: T, mut neighbors: F, mut on_visit: S) -> Option<T>
+where
+    T: Eq + Hash + Copy,
+    F: FnMut(T) -> I,
+    I: IntoIterator<Item = T>,
+    S: FnMut(T, usize) -> ControlFlow<()>,
+{
+    let mut seen = HashSet::new();
+    let mut q = VecDeque::new();
+
+    seen.insert(start);
+    q.push_back((start, 0));
+
+    while let Some((cur, d)) = q.pop_front() {
+        if on_visit(cur, d).is_break() {
+            return Some(cur);
+        }
+        for nb in neighbors(cur) {
+            if seen.insert(nb) {
+                q.push_back((nb, d + 1));
+            }
+        }
+    }
+
+    None
+}
+
+/// Every node at most `max_dist` steps from `start` (start included). The BFS
+/// never expands past `max_dist`, so only the returned set is stored.
+pub fn reachable_within<T, F, I>(start: T, max_dist: usize, mut neighbors: F) -> HashSet<T>
+where
+    T: Eq + Hash + Copy,
+    F: FnMut(T) -> I,
+    I: IntoIterator<Item = T>,
+{
+    let mut seen = HashSet::from([start]);
+    let mut frontier = vec![start];
+
+    for _ in 0..max_dist {
+        let mut next = Vec::new();
+        for cur in frontier {
+            for nb in neighbors(cur) {
+                if seen.insert(nb) {
+                    next.push(nb);
+                }
+            }
+        }
+        if next.is_empty() {
+            break;
+        }
+        frontier = next;
+    }
+
+    seen
+}
+
 /// Depth-first search (non-recursive).
 pub fn dfs<T, F, I>(start: T, mut neighbors: F) -> Vec<T>
 where
@@ -86,6 +179,152 @@ where
     (dist, prev)
 }
 
+/// Dijkstra with deterministic tie-breaking: among queued nodes at the same
+/// distance, the one ordered first by `tie` is expanded first. Otherwise the
+/// same as `dijkstra`; returns (distance map, previous-node map).
+pub fn dijkstra_tiebreak<T, F, I, C>(start: T, mut neighbors: F, tie: C) -> (HashMap<T, i64>, HashMap<T, T>)
+where
+    T: Eq + Hash + Copy,
+    F: FnMut(T) -> I,
+    I: IntoIterator<Item = (T, i64)>, // (neighbor, cost)
+    C: Fn(&T, &T) -> Ordering,
+{
+    let mut dist: HashMap<T, i64> = HashMap::new();
+    let mut prev: HashMap<T, T> = HashMap::new();
+    // distance -> nodes queued at that distance
+    let mut frontier: BTreeMap<i64, Vec<T>> = BTreeMap::new();
+    // the bucket being drained is kept sorted worst-first, so the best node
+    // is popped off the end; other buckets are sorted once when reached
+    let mut sorted: Option<i64> = None;
+
+    dist.insert(start, 0);
+    frontier.entry(0).or_default().push(start);
+
+    while let Some(mut bucket) = frontier.first_entry() {
+        let d = *bucket.key();
+        let nodes = bucket.get_mut();
+        if sorted != Some(d) {
+            nodes.sort_by(|a, b| tie(b, a));
+            sorted = Some(d);
+        }
+        let u = nodes.pop().unwrap();
+        if nodes.is_empty() {
+            bucket.remove();
+        }
+
+        if d > dist[&u] {
+            continue; // outdated entry
+        }
+
+        for (v, w) in neighbors(u) {
+            let nd = d + w;
+            if dist.get(&v).is_none_or(|&old| nd < old) {
+                dist.insert(v, nd);
+                prev.insert(v, u);
+                let queued = frontier.entry(nd).or_default();
+                if sorted == Some(nd) {
+                    // zero-weight edge into the bucket being drained
+                    let at = queued.partition_point(|x| tie(x, &v) == Ordering::Greater);
+                    queued.insert(at, v);
+                } else {
+                    queued.push(v);
+                }
+            }
+        }
+    }
+
+    (dist, prev)
+}
+
+/// 0-1 BFS: shortest distances when every edge weight is 0 or 1.
+///
+/// `neighbors` yields (neighbor, weight) pairs; weights outside {0, 1} are a
+/// logic error (checked with a debug assertion). Faster than `dijkstra` for
+/// this case since a deque replaces the priority queue.
+pub fn bfs01<T, F, I>(start: T, mut neighbors: F) -> HashMap<T, i64>
+where
+    T: Eq + Hash + Copy,
+    F: FnMut(T) -> I,
+    I: IntoIterator<Item = (T, i64)>, // (neighbor, weight in {0, 1})
+{
+    let mut dist: HashMap<T, i64> = HashMap::new();
+    let mut q = VecDeque::new();
+
+    dist.insert(start, 0);
+    q.push_back((start, 0_i64));
+
+    while let Some((u, d)) = q.pop_front() {
+        if d > dist[&u] {
+            continue; // outdated entry
+        }
+
+        for (v, w) in neighbors(u) {
+            debug_assert!(w == 0 || w == 1, "bfs01 edge weight must be 0 or 1, got {w}");
+            let nd = d + w;
+            if dist.get(&v).is_none_or(|&old| nd < old) {
+                dist.insert(v, nd);
+                if w == 0 {
+                    q.push_front((v, nd));
+                } else {
+                    q.push_back((v, nd));
+                }
+            }
+        }
+    }
+
+    dist
+}
+
+/// Dial's algorithm: Dijkstra with a bucket queue, for small integer weights.
+///
+/// Panics if `max_weight` is negative or any edge weight lies outside
+/// `0..=max_weight`. Pending distances always fall within a window of
+/// `max_weight + 1`, so a rolling array of that many buckets replaces the
+/// priority queue; `max_weight` should therefore be small. Returns the
+/// distance map.
+pub fn dijkstra_dial<T, F, I>(start: T, max_weight: i64, mut neighbors: F) -> HashMap<T, i64>
+where
+    T: Eq + Hash + Copy,
+    F: FnMut(T) -> I,
+    I: IntoIterator<Item = (T, i64)>, // (neighbor, weight in 0..=max_weight)
+{
+    assert!(max_weight >= 0, "dijkstra_dial max_weight must be non-negative, got {max_weight}");
+    let size = max_weight as usize + 1;
+    let mut buckets: Vec<Vec<T>> = vec![Vec::new(); size];
+    let mut dist: HashMap<T, i64> = HashMap::new();
+    let mut pending = 1;
+
+    dist.insert(start, 0);
+    buckets[0].push(start);
+
+    let mut d = 0_i64;
+    while pending > 0 {
+        let idx = d as usize % size;
+        while let Some(u) = buckets[idx].pop() {
+            pending -= 1;
+            if dist[&u] != d {
+                continue; // outdated entry
+            }
+
+            for (v, w) in neighbors(u) {
+                assert!(
+                    (0..=max_weight).contains(&w),
+                    "dijkstra_dial edge weight must be in 0..={max_weight}, got {w}"
+                );
+                let nd = d + w;
+                if dist.get(&v).is_none_or(|&old| nd < old) {
+                    dist.insert(v, nd);
+                    buckets[nd as usize % size].push(v);
+                    pending += 1;
+                }
+            }
+        }
+        d += 1;
+    }
+
+    dist
+}
+
 /// Reconstruct path from start to `end` using `prev` map returned by dijkstra.
 pub fn reconstruct_path<T>(prev: &HashMap<T, T>, end: T) -> Vec<T>
 where
@@ -104,135 +343,1048 @@ where
     path
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use std::collections::HashMap;
+/// Dijkstra that keeps every optimal predecessor: returns (distance map,
+/// predecessor map), where `preds[v]` lists each `u` with
+/// `dist[u] + w(u, v) == dist[v]`. Use `reconstruct_all_paths` to enumerate paths.
+/// Weights must be positive, or ties between equal-distance nodes form cycles.
+pub fn dijkstra_all_paths<T, F, I>(start: T, mut neighbors: F) -> (HashMap<T, i64>, HashMap<T, Vec<T>>)
+where
+    T: Eq + Hash + Copy,
+    F: FnMut(T) -> I,
+    I: IntoIterator<Item = (T, i64)>, // (neighbor, cost)
+{
+    let mut dist: HashMap<T, i64> = HashMap::new();
+    let mut preds: HashMap<T, Vec<T>> = HashMap::new();
+    let mut pq = PriorityQueue::new();
 
-    // ---- bfs tests ----
+    dist.insert(start, 0);
+    pq.push(start, std::cmp::Reverse(0_i64));
 
-    #[test]
-    fn bfs_on_simple_line_graph() {
-        // 0 - 1 - 2 - 3
-        let neighbors = |n: i32| -> Vec<i32> {
-            match n {
-                0 => vec![1],
-                1 => vec![0, 2],
-                2 => vec![1, 3],
-                3 => vec![2],
-                _ => vec![],
-            }
-        };
+    while let Some((u, std::cmp::Reverse(d))) = pq.pop() {
+        if d > dist[&u] {
+            continue; // outdated entry
+        }
 
-        let dist = bfs(0, neighbors);
+        for (v, w) in neighbors(u) {
+            let nd = d + w;
+            match dist.get(&v) {
+                Some(&old) if nd > old => {}
+                Some(&old) if nd == old => {
+                    if let Some(p) = preds.get_mut(&v)
+                        && !p.contains(&u)
+                    {
+                        p.push(u);
+                    }
+                }
+                _ => {
+                    dist.insert(v, nd);
+                    preds.insert(v, vec![u]);
+                    pq.push(v, std::cmp::Reverse(nd));
+                }
+            }
+        }
+    }
 
-        let mut expected = HashMap::new();
-        expected.insert(0, 0);
-        expected.insert(1, 1);
-        expected.insert(2, 2);
-        expected.insert(3, 3);
+    (dist, preds)
+}
 
-        assert_eq!(dist, expected);
+/// Every path from the source to `end` through a predecessor map from
+/// `dijkstra_all_paths`, each ordered start..=end. The count can grow
+/// exponentially with the number of tied branches.
+pub fn reconstruct_all_paths<T>(preds: &HashMap<T, Vec<T>>, end: T) -> Vec<Vec<T>>
+where
+    T: Eq + Hash + Copy,
+{
+    match preds.get(&end) {
+        None => vec![vec![end]],
+        Some(ps) => ps
+            .iter()
+            .flat_map(|&p| reconstruct_all_paths(preds, p))
+            .map(|mut path| {
+                path.push(end);
+                path
+            })
+            .collect(),
     }
+}
 
-    #[test]
-    fn bfs_finds_shortest_paths_in_unweighted_graph() {
-        // Graph:
-        //  1
-        //  |
-        //  0 - 2 - 3
-        //   \      /
-        //     4 --
-        //
-        // shortest from 0:
-        // 1:1, 2:1, 4:1, 3:2
-        let neighbors = |n: i32| -> Vec<i32> {
-            match n {
-                0 => vec![1, 2, 4],
-                1 => vec![0],
-                2 => vec![0, 3],
-                3 => vec![2, 4],
-                4 => vec![0, 3],
-                _ => vec![],
-            }
-        };
+/// Find a directed cycle reachable from any of `nodes`.
+///
+/// Returns the nodes of one cycle in order, so that each node has an edge to
+/// the next and the last has an edge back to the first. Returns `None` if the
+/// graph is acyclic.
+pub fn find_cycle<T, F, I>(nodes: impl IntoIterator<Item = T>, mut neighbors: F) -> Option<Vec<T>>
+where
+    T: Eq + Hash + Copy,
+    F: FnMut(T) -> I,
+    I: IntoIterator<Item = T>,
+{
+    // Nodes on the current DFS path are "gray"; fully explored nodes are "black".
+    let mut done: HashSet<T> = HashSet::new();
+    let mut on_path: HashSet<T> = HashSet::new();
 
-        let dist = bfs(0, neighbors);
+    for root in nodes {
+        if done.contains(&root) {
+            continue;
+        }
 
-        assert_eq!(dist.get(&0), Some(&0));
-        assert_eq!(dist.get(&1), Some(&1));
-        assert_eq!(dist.get(&2), Some(&1));
-        assert_eq!(dist.get(&4), Some(&1));
-        assert_eq!(dist.get(&3), Some(&2));
+        let mut path = vec![root];
+        let mut stack = vec![neighbors(root).into_iter()];
+        on_path.insert(root);
+
+        while let Some(iter) = stack.last_mut() {
+            match iter.next() {
+                Some(nb) if on_path.contains(&nb) => {
+                    let start = path.iter().position(|&n| n == nb).unwrap();
+                    return Some(path[start..].to_vec());
+                }
+                Some(nb) => {
+                    if done.contains(&nb) {
+                        continue;
+                    }
+                    on_path.insert(nb);
+                    path.push(nb);
+                    stack.push(neighbors(nb).into_iter());
+                }
+                None => {
+                    stack.pop();
+                    let cur = path.pop().unwrap();
+                    on_path.remove(&cur);
+                    done.insert(cur);
+                }
+            }
+        }
     }
 
-    #[test]
-    fn bfs_on_isolated_node() {
-        let neighbors = |_n: i32| -> Vec<i32> { vec![] };
-        let dist = bfs(42, neighbors);
+    None
+}
 
-        assert_eq!(dist.len(), 1);
-        assert_eq!(dist.get(&42), Some(&0));
-    }
+/// Strongly connected components via Tarjan's algorithm (iterative).
+///
+/// Each inner Vec is one component; a node without a self-loop that is not on
+/// any cycle forms its own singleton component. Components are returned in
+/// reverse topological order (sinks first).
+pub fn scc<T, F, I>(nodes: impl IntoIterator<Item = T>, mut neighbors: F) -> Vec<Vec<T>>
+where
+    T: Eq + Hash + Copy,
+    F: FnMut(T) -> I,
+    I: IntoIterator<Item = T>,
+{
+    let mut index: HashMap<T, usize> = HashMap::new();
+    let mut low: HashMap<T, usize> = HashMap::new();
+    let mut on_stack: HashSet<T> = HashSet::new();
+    let mut stack: Vec<T> = Vec::new();
+    let mut components = Vec::new();
 
-    // ---- dfs tests ----
+    for root in nodes {
+        if index.contains_key(&root) {
+            continue;
+        }
 
-    #[test]
-    fn dfs_order_with_deterministic_neighbors() {
-        // Directed-like graph (via neighbor function):
-        // A -> B, C
-        // B -> D
-        // C -> D
-        //
-        // Stack-based DFS with neighbors in the given order.
-        // Start: 'A'
-        //
-        // stack process:
-        //   start: ['A']
-        //   pop A -> push neighbors B, C => stack [B, C]
-        //   pop C -> push neighbor D => stack [B, D]
-        //   pop D -> no neighbors => stack [B]
-        //   pop B -> neighbor D already visited
-        //
-        // visit order: A, C, D, B
-        let neighbors = |n: char| -> Vec<char> {
-            match n {
-                'A' => vec!['B', 'C'],
-                'B' => vec!['D'],
-                'C' => vec!['D'],
-                'D' => vec![],
-                _ => vec![],
+        index.insert(root, index.len());
+        low.insert(root, index[&root]);
+        stack.push(root);
+        on_stack.insert(root);
+        let mut call = vec![(root, neighbors(root).into_iter())];
+
+        while let Some((v, iter)) = call.last_mut() {
+            let v = *v;
+            if let Some(w) = iter.next() {
+                if !index.contains_key(&w) {
+                    index.insert(w, index.len());
+                    low.insert(w, index[&w]);
+                    stack.push(w);
+                    on_stack.insert(w);
+                    call.push((w, neighbors(w).into_iter()));
+                } else if on_stack.contains(&w) {
+                    let lw = index[&w];
+                    let lv = low.get_mut(&v).unwrap();
+                    *lv = (*lv).min(lw);
+                }
+                continue;
             }
-        };
 
-        let order = dfs('A', neighbors);
-        assert_eq!(order, vec!['A', 'C', 'D', 'B']);
+            // all neighbors of v explored
+            call.pop();
+            let lv = low[&v];
+            if let Some((parent, _)) = call.last() {
+                let lp = low.get_mut(parent).unwrap();
+                *lp = (*lp).min(lv);
+            }
+
+            if lv == index[&v] {
+                let mut component = Vec::new();
+                while let Some(w) = stack.pop() {
+                    on_stack.remove(&w);
+                    component.push(w);
+                    if w == v {
+                        break;
+                    }
+                }
+                components.push(component);
+            }
+        }
     }
 
-    #[test]
-    fn dfs_on_isolated_node() {
-        let neighbors = |_n: i32| -> Vec<i32> { vec![] };
-        let order = dfs(7, neighbors);
+    components
+}
+
+/// Union-find over arbitrary hashable nodes, with path compression and
+/// union by size. Nodes are added lazily the first time they are seen.
+#[derive(Debug, Clone)]
+pub struct DisjointSet<T> {
+    parent: HashMap<T, T>,
+    size: HashMap<T, usize>,
+}
+
+impl<T> Default for DisjointSet<T> {
+    fn default() -> Self {
+        Self { parent: HashMap::new(), size: HashMap::new() }
+    }
+}
+
+impl<T> DisjointSet<T>
+where
+    T: Eq + Hash + Copy,
+{
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Representative of the set containing `x`.
+    pub fn find(&mut self, x: T) -> T {
+        if let Entry::Vacant(e) = self.parent.entry(x) {
+            e.insert(x);
+            self.size.insert(x, 1);
+            return x;
+        }
+
+        let mut root = x;
+        while self.parent[&root] != root {
+            root = self.parent[&root];
+        }
+
+        // path compression
+        let mut cur = x;
+        while cur != root {
+            let next = self.parent[&cur];
+            self.parent.insert(cur, root);
+            cur = next;
+        }
+        root
+    }
+
+    /// Merge the sets containing `a` and `b`. Returns false if already joined.
+    pub fn union(&mut self, a: T, b: T) -> bool {
+        let (ra, rb) = (self.find(a), self.find(b));
+        if ra == rb {
+            return false;
+        }
+        let (big, small) = if self.size[&ra] >= self.size[&rb] { (ra, rb) } else { (rb, ra) };
+        self.parent.insert(small, big);
+        let moved = self.size[&small];
+        *self.size.get_mut(&big).unwrap() += moved;
+        true
+    }
+}
+
+/// Kruskal's minimum spanning tree: returns (total weight, chosen edges).
+///
+/// For a disconnected graph this is a minimum spanning forest.
+pub fn mst<T>(edges: &[(T, T, i64)]) -> (i64, Vec<(T, T, i64)>)
+where
+    T: Eq + Hash + Copy,
+{
+    let mut sorted = edges.to_vec();
+    sorted.sort_by_key(|&(_, _, w)| w);
+
+    let mut sets = DisjointSet::new();
+    let mut total = 0;
+    let mut chosen = Vec::new();
+
+    for (a, b, w) in sorted {
+        if sets.union(a, b) {
+            total += w;
+            chosen.push((a, b, w));
+        }
+    }
+
+    (total, chosen)
+}
+
+/// Dijkstra that stops as soon as `goal` is popped from the queue.
+/// Returns (cost, path from start to goal), or None if `goal` is unreachable.
+pub fn dijkstra_to<T, F, I>(start: T, goal: T, mut neighbors: F) -> Option<(i64, Vec<T>)>
+where
+    T: Eq + Hash + Copy,
+    F: FnMut(T) -> I,
+    I: IntoIterator<Item = (T, i64)>, // (neighbor, cost)
+{
+    let mut dist: HashMap<T, i64> = HashMap::new();
+    let mut prev: HashMap<T, T> = HashMap::new();
+    let mut pq = PriorityQueue::new();
+
+    dist.insert(start, 0);
+    pq.push(start, std::cmp::Reverse(0_i64));
+
+    while let Some((u, std::cmp::Reverse(d))) = pq.pop() {
+        if d > dist[&u] {
+            continue; // outdated entry
+        }
+        if u == goal {
+            return Some((d, reconstruct_path(&prev, goal)));
+        }
+
+        for (v, w) in neighbors(u) {
+            let nd = d + w;
+            if dist.get(&v).is_none_or(|&old| nd < old) {
+                dist.insert(v, nd);
+                prev.insert(v, u);
+                pq.push(v, std::cmp::Reverse(nd));
+            }
+        }
+    }
+
+    None
+}
+
+/// Greedy best-first search: always expands the queued node with the lowest
+/// `heuristic`, ignoring path cost so far. Usually much faster than Dijkstra
+/// but the returned path (start..=goal) is the first one found, not
+/// necessarily the shortest. None if `goal` is unreachable.
+pub fn greedy_best_first<T, F, I, H>(start: T, goal: T, mut neighbors: F, mut heuristic: H) -> Option<Vec<T>>
+where
+    T: Eq + Hash + Copy,
+    F: FnMut(T) -> I,
+    I: IntoIterator<Item = T>,
+    H: FnMut(T) -> i64,
+{
+    let mut prev: HashMap<T, T> = HashMap::new();
+    let mut seen = HashSet::new();
+    let mut pq = PriorityQueue::new();
+
+    seen.insert(start);
+    pq.push(start, std::cmp::Reverse(heuristic(start)));
+
+    while let Some((u, _)) = pq.pop() {
+        if u == goal {
+            return Some(reconstruct_path(&prev, goal));
+        }
+        for v in neighbors(u) {
+            if seen.insert(v) {
+                prev.insert(v, u);
+                pq.push(v, std::cmp::Reverse(heuristic(v)));
+            }
+        }
+    }
+
+    None
+}
+
+/// Iterative deepening DFS: runs depth-limited DFS with limits 0, 1, ...,
+/// `max_depth` and returns the first path found from `start` to `goal`
+/// (a shortest one, with `path.len() - 1` edges), or None if `goal` is not
+/// within `max_depth` steps. Uses memory proportional to the depth only.
+pub fn iddfs<T, F, I>(start: T, goal: T, max_depth: usize, mut neighbors: F) -> Option<Vec<T>>
+where
+    T: Eq + Hash + Copy,
+    F: FnMut(T) -> I,
+    I: IntoIterator<Item = T>,
+{
+    fn dls<T, F, I>(cur: T, goal: T, limit: usize, path: &mut Vec<T>, neighbors: &mut F) -> bool
+    where
+        T: Eq + Hash + Copy,
+        F: FnMut(T) -> I,
+        I: IntoIterator<Item = T>,
+    {
+        if cur == goal {
+            return true;
+        }
+        if limit == 0 {
+            return false;
+        }
+        for nb in neighbors(cur) {
+            if path.contains(&nb) {
+                continue; // don't walk in circles along the current path
+            }
+            path.push(nb);
+            if dls(nb, goal, limit - 1, path, neighbors) {
+                return true; // leave the path in place for the caller
+            }
+            path.pop();
+        }
+        false
+    }
+
+    (0..=max_depth).find_map(|limit| {
+        let mut path = vec![start];
+        dls(start, goal, limit, &mut path, &mut neighbors).then_some(path)
+    })
+}
+
+/// Collapse chains of degree-2 nodes into weighted edges between junctions.
+///
+/// Junctions are the nodes in `nodes` whose degree is not 2. Returns, for each
+/// junction, the junctions reachable along a corridor and the corridor length
+/// (number of edges). Assumes an undirected graph (`neighbors` is symmetric);
+/// corridors leading back to the same junction are dropped.
+pub fn contract_corridors<T, F, I>(
+    nodes: impl IntoIterator<Item = T>,
+    mut neighbors: F,
+) -> HashMap<T, Vec<(T, i64)>>
+where
+    T: Eq + Hash + Copy,
+    F: FnMut(T) -> I,
+    I: IntoIterator<Item = T>,
+{
+    let adj: HashMap<T, Vec<T>> = nodes
+        .into_iter()
+        .map(|n| (n, neighbors(n).into_iter().collect()))
+        .collect();
+    let is_junction = |n: &T| adj.get(n).is_none_or(|nbs| nbs.len() != 2);
+
+    let mut out = HashMap::new();
+    for (&j, nbs) in adj.iter().filter(|(n, _)| is_junction(n)) {
+        let mut edges = Vec::new();
+        for &first in nbs {
+            let (mut prev, mut cur, mut len) = (j, first, 1);
+            while !is_junction(&cur) {
+                let next = adj[&cur].iter().copied().find(|&n| n != prev).unwrap_or(prev);
+                (prev, cur) = (cur, next);
+                len += 1;
+            }
+            if cur != j {
+                edges.push((cur, len));
+            }
+        }
+        out.insert(j, edges);
+    }
+
+    out
+}
+
+/// A memoization cache for recursive functions.
+///
+/// The compute closure receives `&mut Memo` itself so it can recurse through
+/// the cache, e.g. `memo.get_or_compute(n, |m| fib(m, n - 1) + fib(m, n - 2))`.
+/// This borrows fine because `get_or_compute` only touches the map before and
+/// after calling `f`, never while `f` holds the `&mut Self`. Values are cloned
+/// out, so keep `V` cheap to clone.
+#[derive(Debug, Clone)]
+pub struct Memo<K, V> {
+    cache: HashMap<K, V>,
+}
+
+impl<K, V> Default for Memo<K, V> {
+    fn default() -> Self {
+        Self { cache: HashMap::new() }
+    }
+}
+
+impl<K, V> Memo<K, V>
+where
+    K: Eq + Hash + Clone,
+    V: Clone,
+{
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Cached value for `key`, computing and storing it with `f` on a miss.
+    pub fn get_or_compute(&mut self, key: K, f: impl FnOnce(&mut Self) -> V) -> V {
+        if let Some(v) = self.cache.get(&key) {
+            return v.clone();
+        }
+        let v = f(self);
+        self.cache.insert(key, v.clone());
+        v
+    }
+
+    /// Cached value for `key`, if already computed.
+    pub fn get(&self, key: &K) -> Option<&V> {
+        self.cache.get(key)
+    }
+
+    pub fn len(&self) -> usize {
+        self.cache.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cache.is_empty()
+    }
+}
+
+/// Floyd's tortoise-and-hare on the sequence start, f(start), f(f(start)), ...
+///
+/// Returns (mu, lambda): the index where the cycle begins and its length.
+/// Uses constant memory, but calls `f` several times per element, so prefer a
+/// seen-state map when `f` is expensive and the states are small.
+pub fn floyd_cycle<T: Eq, F: FnMut(&T) -> T>(start: T, mut f: F) -> (usize, usize) {
+    // phase 1: find a meeting point inside the cycle
+    let mut tortoise = f(&start);
+    let mut hare = f(&tortoise);
+    while tortoise != hare {
+        tortoise = f(&tortoise);
+        let step = f(&hare);
+        hare = f(&step);
+    }
+
+    // phase 2: find the start of the cycle
+    let mut mu = 0;
+    tortoise = start;
+    while tortoise != hare {
+        tortoise = f(&tortoise);
+        hare = f(&hare);
+        mu += 1;
+    }
+
+    // phase 3: find the cycle length
+    let mut lambda = 1;
+    hare = f(&tortoise);
+    while tortoise != hare {
+        hare = f(&hare);
+        lambda += 1;
+    }
+
+    (mu, lambda)
+}
+
+/// Assign each key a distinct value from its candidate set by repeatedly
+/// fixing keys with a single remaining candidate and striking that value from
+/// the others. Returns None if propagation gets stuck (no unique assignment
+/// can be deduced this way) or a key runs out of candidates.
+pub fn solve_assignment<K, V>(candidates: &HashMap<K, HashSet<V>>) -> Option<HashMap<K, V>>
+where
+    K: Eq + Hash + Clone,
+    V: Eq + Hash + Clone,
+{
+    let mut open = candidates.clone();
+    let mut fixed = HashMap::new();
+
+    while !open.is_empty() {
+        let (key, value) = open.iter().find_map(|(k, vs)| match vs.len() {
+            1 => vs.iter().next().map(|v| (k.clone(), v.clone())),
+            _ => None,
+        })?;
+        open.remove(&key);
+        for vs in open.values_mut() {
+            vs.remove(&value);
+        }
+        fixed.insert(key, value);
+    }
+
+    Some(fixed)
+}
+
+/// Maximum flow from `source` to `sink` over directed `edges` of
+/// (from, to, capacity), by Edmonds-Karp (BFS augmenting paths). Parallel
+/// edges add their capacities; add both directions for an undirected edge.
+pub fn max_flow<T>(edges: &[(T, T, i64)], source: T, sink: T) -> i64
+where
+    T: Eq + Hash + Copy,
+{
+    edmonds_karp(edges, source, sink).0
+}
+
+/// Edges of a minimum `source`-`sink` cut: every (from, to) in `edges` leading
+/// from the source side of the saturated residual graph to the other side.
+/// Their capacities sum to `max_flow`.
+pub fn min_cut<T>(edges: &[(T, T, i64)], source: T, sink: T) -> Vec<(T, T)>
+where
+    T: Eq + Hash + Copy,
+{
+    let (_, source_side) = edmonds_karp(edges, source, sink);
+    edges
+        .iter()
+        .filter(|(u, v, _)| source_side.contains(u) && !source_side.contains(v))
+        .map(|&(u, v, _)| (u, v))
+        .collect()
+}
+
+/// (max flow, nodes still reachable from the source in the final residual graph)
+fn edmonds_karp<T>(edges: &[(T, T, i64)], source: T, sink: T) -> (i64, HashSet<T>)
+where
+    T: Eq + Hash + Copy,
+{
+    let mut residual: HashMap<(T, T), i64> = HashMap::new();
+    let mut adj: HashMap<T, Vec<T>> = HashMap::new();
+    for &(u, v, c) in edges {
+        if !residual.contains_key(&(u, v)) && !residual.contains_key(&(v, u)) {
+            adj.entry(u).or_default().push(v);
+            adj.entry(v).or_default().push(u);
+        }
+        *residual.entry((u, v)).or_default() += c;
+        residual.entry((v, u)).or_default();
+    }
+
+    let mut flow = 0;
+    loop {
+        let mut prev: HashMap<T, T> = HashMap::new();
+        let mut seen = HashSet::from([source]);
+        let mut q = VecDeque::from([source]);
+        while let Some(u) = q.pop_front() {
+            if u == sink {
+                break;
+            }
+            for &v in adj.get(&u).into_iter().flatten() {
+                if residual[&(u, v)] > 0 && seen.insert(v) {
+                    prev.insert(v, u);
+                    q.push_back(v);
+                }
+            }
+        }
+        if source == sink || !seen.contains(&sink) {
+            return (flow, seen);
+        }
+
+        let path = reconstruct_path(&prev, sink);
+        let push = path.windows(2).map(|w| residual[&(w[0], w[1])]).min().unwrap_or(0);
+        for w in path.windows(2) {
+            *residual.get_mut(&(w[0], w[1])).unwrap() -= push;
+            *residual.get_mut(&(w[1], w[0])).unwrap() += push;
+        }
+        flow += push;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    // ---- bfs tests ----
+
+    #[test]
+    fn bfs_on_simple_line_graph() {
+        // 0 - 1 - 2 - 3
+        let neighbors = |n: i32| -> Vec<i32> {
+            match n {
+                0 => vec![1],
+                1 => vec![0, 2],
+                2 => vec![1, 3],
+                3 => vec![2],
+                _ => vec![],
+            }
+        };
+
+        let dist = bfs(0, neighbors);
+
+        let mut expected = HashMap::new();
+        expected.insert(0, 0);
+        expected.insert(1, 1);
+        expected.insert(2, 2);
+        expected.insert(3, 3);
+
+        assert_eq!(dist, expected);
+    }
+
+    #[test]
+    fn bfs_finds_shortest_paths_in_unweighted_graph() {
+        // Graph:
+        //  1
+        //  |
+        //  0 - 2 - 3
+        //   \      /
+        //     4 --
+        //
+        // shortest from 0:
+        // 1:1, 2:1, 4:1, 3:2
+        let neighbors = |n: i32| -> Vec<i32> {
+            match n {
+                0 => vec![1, 2, 4],
+                1 => vec![0],
+                2 => vec![0, 3],
+                3 => vec![2, 4],
+                4 => vec![0, 3],
+                _ => vec![],
+            }
+        };
+
+        let dist = bfs(0, neighbors);
+
+        assert_eq!(dist.get(&0), Some(&0));
+        assert_eq!(dist.get(&1), Some(&1));
+        assert_eq!(dist.get(&2), Some(&1));
+        assert_eq!(dist.get(&4), Some(&1));
+        assert_eq!(dist.get(&3), Some(&2));
+    }
+
+    #[test]
+    fn bfs_on_isolated_node() {
+        let neighbors = |_n: i32| -> Vec<i32> { vec![] };
+        let dist = bfs(42, neighbors);
+
+        assert_eq!(dist.len(), 1);
+        assert_eq!(dist.get(&42), Some(&0));
+    }
+
+    // ---- bfs_visit tests ----
+
+    #[test]
+    fn bfs_visit_matches_bfs_when_never_breaking() {
+        let neighbors = |n: i32| -> Vec<i32> { vec![(n * 2) % 37, (n + 5) % 37] };
+
+        let mut visited = Vec::new();
+        let stopped = bfs_visit(1, neighbors, |n, d| {
+            visited.push((n, d));
+            ControlFlow::Continue(())
+        });
+
+        assert_eq!(stopped, None);
+        assert!(visited.windows(2).all(|w| w[0].1 <= w[1].1));
+        let as_map: HashMap<i32, usize> = visited.into_iter().collect();
+        assert_eq!(as_map, bfs(1, neighbors));
+    }
+
+    #[test]
+    fn bfs_visit_break_stops_exploration() {
+        let mut calls = 0;
+        let stopped = bfs_visit(0, |n: i32| vec![n + 1], |n, d| {
+            calls += 1;
+            assert_eq!(n as usize, d);
+            if n == 5 { ControlFlow::Break(()) } else { ControlFlow::Continue(()) }
+        });
+
+        assert_eq!(stopped, Some(5));
+        assert_eq!(calls, 6);
+    }
+
+    // ---- reachable_within tests ----
+
+    #[test]
+    fn reachable_within_is_a_diamond_on_open_grid() {
+        let neighbors = |(x, y): (i32, i32)| [(x + 1, y), (x - 1, y), (x, y + 1), (x, y - 1)];
+        for r in 0..5 {
+            let area = reachable_within((0, 0), r, neighbors).len();
+            assert_eq!(area, 2 * r * r + 2 * r + 1, "r = {r}");
+        }
+    }
+
+    #[test]
+    fn reachable_within_matches_filtered_bfs() {
+        let neighbors = |n: i32| -> Vec<i32> { vec![(n * 3) % 50, (n + 7) % 50] };
+        let expected: HashSet<i32> = bfs(1, neighbors)
+            .into_iter()
+            .filter(|&(_, d)| d <= 3)
+            .map(|(n, _)| n)
+            .collect();
+        assert_eq!(reachable_within(1, 3, neighbors), expected);
+    }
+
+    // ---- dfs tests ----
+
+    #[test]
+    fn dfs_order_with_deterministic_neighbors() {
+        // Directed-like graph (via neighbor function):
+        // A -> B, C
+        // B -> D
+        // C -> D
+        //
+        // Stack-based DFS with neighbors in the given order.
+        // Start: 'A'
+        //
+        // stack process:
+        //   start: ['A']
+        //   pop A -> push neighbors B, C => stack [B, C]
+        //   pop C -> push neighbor D => stack [B, D]
+        //   pop D -> no neighbors => stack [B]
+        //   pop B -> neighbor D already visited
+        //
+        // visit order: A, C, D, B
+        let neighbors = |n: char| -> Vec<char> {
+            match n {
+                'A' => vec!['B', 'C'],
+                'B' => vec!['D'],
+                'C' => vec!['D'],
+                'D' => vec![],
+                _ => vec![],
+            }
+        };
+
+        let order = dfs('A', neighbors);
+        assert_eq!(order, vec!['A', 'C', 'D', 'B']);
+    }
+
+    #[test]
+    fn dfs_on_isolated_node() {
+        let neighbors = |_n: i32| -> Vec<i32> { vec![] };
+        let order = dfs(7, neighbors);
         assert_eq!(order, vec![7]);
     }
 
-    // ---- dijkstra tests ----
+    // ---- dijkstra tests ----
+
+    #[test]
+    fn dijkstra_on_simple_weighted_graph() {
+        // Weighted graph:
+        //
+        // A -1-> B -2-> D
+        //  \           ^
+        //   5         /
+        //    \       / 1
+        //     v     /
+        //      C -- 
+        //
+        // Paths from A:
+        //   A -> B -> D: 1 + 2 = 3 (shortest)
+        //   A -> C -> D: 5 + 1 = 6
+        //
+        let neighbors = |n: char| -> Vec<(char, i64)> {
+            match n {
+                'A' => vec![('B', 1), ('C', 5)],
+                'B' => vec![('D', 2)],
+                'C' => vec![('D', 1)],
+                'D' => vec![],
+                _ => vec![],
+            }
+        };
+
+        let (dist, prev) = dijkstra('A', neighbors);
+
+        assert_eq!(dist.get(&'A'), Some(&0));
+        assert_eq!(dist.get(&'B'), Some(&1));
+        assert_eq!(dist.get(&'D'), Some(&3));
+
+        // prev should show that the best path is A -> B -> D
+        assert_eq!(prev.get(&'B'), Some(&'A'));
+        assert_eq!(prev.get(&'D'), Some(&'B'));
+
+        // reconstruct the path A -> D
+        let path = reconstruct_path(&prev, 'D');
+        assert_eq!(path, vec!['A', 'B', 'D']);
+    }
+
+    #[test]
+    fn dijkstra_handles_outdated_queue_entries() {
+        // Graph where we first find a worse path to C, then a better one.
+        //
+        // A -> B (cost 5)
+        // A -> C (cost 10)
+        // B -> C (cost 1)  => better path A->B->C with cost 6
+        //
+        let neighbors = |n: char| -> Vec<(char, i64)> {
+            match n {
+                'A' => vec![('B', 5), ('C', 10)],
+                'B' => vec![('C', 1)],
+                'C' => vec![],
+                _ => vec![],
+            }
+        };
+
+        let (dist, prev) = dijkstra('A', neighbors);
+
+        assert_eq!(dist.get(&'A'), Some(&0));
+        assert_eq!(dist.get(&'B'), Some(&5));
+        assert_eq!(dist.get(&'C'), Some(&6));
+
+        assert_eq!(prev.get(&'B'), Some(&'A'));
+        assert_eq!(prev.get(&'C'), Some(&'B'));
+
+        let path = reconstruct_path(&prev, 'C');
+        assert_eq!(path, vec!['A', 'B', 'C']);
+    }
+
+    #[test]
+    fn dijkstra_with_isolated_start() {
+        let neighbors = |_n: i32| -> Vec<(i32, i64)> { vec![] };
+        let (dist, prev) = dijkstra(0, neighbors);
+
+        assert_eq!(dist.len(), 1);
+        assert_eq!(dist.get(&0), Some(&0));
+        assert!(prev.is_empty());
+    }
+
+    // ---- reconstruct_path tests ----
+
+    #[test]
+    fn reconstruct_path_trivial() {
+        // No parents in map, path should just be [end]
+        let prev: HashMap<i32, i32> = HashMap::new();
+        let path = reconstruct_path(&prev, 99);
+        assert_eq!(path, vec![99]);
+    }
+
+    #[test]
+    fn reconstruct_path_longer_chain() {
+        // prev: 3 <- 2 <- 1
+        let mut prev = HashMap::new();
+        prev.insert(3, 2);
+        prev.insert(2, 1);
+
+        let path = reconstruct_path(&prev, 3);
+        assert_eq!(path, vec![1, 2, 3]);
+    }
+
+    // ---- dijkstra_all_paths tests ----
+
+    #[test]
+    fn dijkstra_all_paths_diamond_returns_both() {
+        // 0 -> 1 -> 3 and 0 -> 2 -> 3, both cost 2; 0 -> 3 direct costs 5
+        let neighbors = |n: i32| -> Vec<(i32, i64)> {
+            match n {
+                0 => vec![(1, 1), (2, 1), (3, 5)],
+                1 | 2 => vec![(3, 1)],
+                _ => vec![],
+            }
+        };
+
+        let (dist, preds) = dijkstra_all_paths(0, neighbors);
+        assert_eq!(dist[&3], 2);
+
+        let mut paths = reconstruct_all_paths(&preds, 3);
+        paths.sort();
+        assert_eq!(paths, vec![vec![0, 1, 3], vec![0, 2, 3]]);
+        assert_eq!(reconstruct_all_paths(&preds, 0), vec![vec![0]]);
+    }
+
+    #[test]
+    fn dijkstra_all_paths_ignores_edges_back_to_start() {
+        let neighbors = |n: i32| -> Vec<(i32, i64)> { vec![((n + 1) % 3, 1)] };
+        let (_, preds) = dijkstra_all_paths(0, neighbors);
+        assert_eq!(reconstruct_all_paths(&preds, 2), vec![vec![0, 1, 2]]);
+    }
+
+    // ---- find_cycle tests ----
+
+    #[test]
+    fn find_cycle_returns_three_node_cycle() {
+        // 0 -> 1 -> 2 -> 3 -> 1
+        let neighbors = |n: i32| -> Vec<i32> {
+            match n {
+                0 => vec![1],
+                1 => vec![2],
+                2 => vec![3],
+                3 => vec![1],
+                _ => vec![],
+            }
+        };
+
+        let cycle = find_cycle([0, 1, 2, 3], neighbors).expect("graph has a cycle");
+        assert_eq!(cycle.len(), 3);
+        for w in cycle.windows(2) {
+            assert!(neighbors(w[0]).contains(&w[1]));
+        }
+        // endpoints connect
+        assert!(neighbors(*cycle.last().unwrap()).contains(&cycle[0]));
+    }
+
+    #[test]
+    fn find_cycle_on_dag_returns_none() {
+        // diamond: A -> B, C; B -> D; C -> D
+        let neighbors = |n: char| -> Vec<char> {
+            match n {
+                'A' => vec!['B', 'C'],
+                'B' => vec!['D'],
+                'C' => vec!['D'],
+                _ => vec![],
+            }
+        };
+
+        assert_eq!(find_cycle(['A', 'B', 'C', 'D'], neighbors), None);
+    }
+
+    #[test]
+    fn find_cycle_detects_self_loop() {
+        let neighbors = |n: i32| -> Vec<i32> { if n == 5 { vec![5] } else { vec![] } };
+        assert_eq!(find_cycle([1, 5], neighbors), Some(vec![5]));
+    }
+
+    // ---- scc tests ----
+
+    #[test]
+    fn scc_finds_two_components() {
+        // {1, 2, 3} form a cycle, {4, 5} form a cycle, 3 -> 4 links them.
+        let neighbors = |n: i32| -> Vec<i32> {
+            match n {
+                1 => vec![2],
+                2 => vec![3],
+                3 => vec![1, 4],
+                4 => vec![5],
+                5 => vec![4],
+                _ => vec![],
+            }
+        };
+
+        let mut comps = scc([1, 2, 3, 4, 5], neighbors);
+        for c in comps.iter_mut() {
+            c.sort();
+        }
+        comps.sort();
+
+        assert_eq!(comps, vec![vec![1, 2, 3], vec![4, 5]]);
+    }
+
+    #[test]
+    fn scc_on_dag_yields_singletons() {
+        let neighbors = |n: char| -> Vec<char> {
+            match n {
+                'A' => vec!['B', 'C'],
+                'B' => vec!['D'],
+                'C' => vec!['D'],
+                _ => vec![],
+            }
+        };
+
+        let comps = scc(['A', 'B', 'C', 'D'], neighbors);
+        assert_eq!(comps.len(), 4);
+        assert!(comps.iter().all(|c| c.len() == 1));
+        // sinks come first
+        assert_eq!(comps[0], vec!['D']);
+    }
+
+    // ---- DisjointSet tests ----
 
     #[test]
-    fn dijkstra_on_simple_weighted_graph() {
-        // Weighted graph:
-        //
-        // A -1-> B -2-> D
-        //  \           ^
-        //   5         /
-        //    \       / 1
-        //     v     /
-        //      C -- 
-        //
-        // Paths from A:
-        //   A -> B -> D: 1 + 2 = 3 (shortest)
-        //   A -> C -> D: 5 + 1 = 6
-        //
+    fn disjoint_set_union_and_find() {
+        let mut ds = DisjointSet::new();
+        assert!(ds.union(1, 2));
+        assert!(ds.union(3, 4));
+        assert!(!ds.union(2, 1)); // already joined
+        assert_eq!(ds.find(1), ds.find(2));
+        assert_ne!(ds.find(1), ds.find(3));
+
+        assert!(ds.union(2, 4));
+        assert_eq!(ds.find(1), ds.find(3));
+        assert_eq!(ds.find(9), 9); // unseen node is its own set
+    }
+
+    // ---- mst tests ----
+
+    #[test]
+    fn mst_on_small_weighted_graph() {
+        //   A --1-- B
+        //   | \     |
+        //   4   3   2
+        //   |     \ |
+        //   D --5-- C
+        let edges = [
+            ('A', 'B', 1),
+            ('B', 'C', 2),
+            ('A', 'C', 3),
+            ('A', 'D', 4),
+            ('C', 'D', 5),
+        ];
+
+        let (total, chosen) = mst(&edges);
+        assert_eq!(total, 1 + 2 + 4);
+        assert_eq!(chosen.len(), 4 - 1);
+    }
+
+    #[test]
+    fn mst_on_disconnected_graph_is_a_forest() {
+        // two components: {1, 2, 3} and {10, 11}
+        let edges = [(1, 2, 5), (2, 3, 1), (1, 3, 2), (10, 11, 7)];
+
+        let (total, chosen) = mst(&edges);
+        assert_eq!(total, 1 + 2 + 7);
+        assert_eq!(chosen.len(), 5 - 2); // nodes - components
+    }
+
+    // ---- dijkstra_to tests ----
+
+    #[test]
+    fn dijkstra_to_matches_full_dijkstra() {
+        // same graph as dijkstra_on_simple_weighted_graph
         let neighbors = |n: char| -> Vec<(char, i64)> {
             match n {
                 'A' => vec![('B', 1), ('C', 5)],
@@ -244,78 +1396,474 @@ mod tests {
         };
 
         let (dist, prev) = dijkstra('A', neighbors);
+        let expected = (dist[&'D'], reconstruct_path(&prev, 'D'));
 
-        assert_eq!(dist.get(&'A'), Some(&0));
-        assert_eq!(dist.get(&'B'), Some(&1));
-        assert_eq!(dist.get(&'D'), Some(&3));
+        assert_eq!(dijkstra_to('A', 'D', neighbors), Some(expected));
+        assert_eq!(dijkstra_to('A', 'D', neighbors), Some((3, vec!['A', 'B', 'D'])));
+    }
 
-        // prev should show that the best path is A -> B -> D
-        assert_eq!(prev.get(&'B'), Some(&'A'));
+    #[test]
+    fn dijkstra_to_start_is_goal() {
+        let neighbors = |_n: i32| -> Vec<(i32, i64)> { vec![] };
+        assert_eq!(dijkstra_to(4, 4, neighbors), Some((0, vec![4])));
+    }
+
+    #[test]
+    fn dijkstra_to_unreachable_goal_returns_none() {
+        let neighbors = |n: i32| -> Vec<(i32, i64)> {
+            match n {
+                0 => vec![(1, 1)],
+                1 => vec![(0, 1)],
+                _ => vec![],
+            }
+        };
+
+        assert_eq!(dijkstra_to(0, 9, neighbors), None);
+    }
+
+    // ---- greedy_best_first tests ----
+
+    fn maze_neighbors(maze: &[&str]) -> impl Fn((i32, i32)) -> Vec<(i32, i32)> + Copy {
+        move |(x, y)| {
+            [(x + 1, y), (x - 1, y), (x, y + 1), (x, y - 1)]
+                .into_iter()
+                .filter(|&(nx, ny)| {
+                    maze.get(ny as usize)
+                        .and_then(|r| r.as_bytes().get(nx as usize))
+                        .is_some_and(|&c| c != b'#')
+                })
+                .collect()
+        }
+    }
+
+    #[test]
+    fn greedy_best_first_reaches_goal_on_grid() {
+        let maze = [
+            "..........",
+            "...S......",
+            "#########.",
+            "..........",
+            "...G......",
+        ];
+        let (start, goal) = ((3, 1), (3, 4));
+        let neighbors = maze_neighbors(&maze);
+        let h = |(x, y): (i32, i32)| ((x - goal.0).abs() + (y - goal.1).abs()) as i64;
+
+        let path = greedy_best_first(start, goal, neighbors, h).unwrap();
+        assert_eq!((path[0], *path.last().unwrap()), (start, goal));
+        assert!(path.windows(2).all(|w| neighbors(w[0]).contains(&w[1])));
+    }
+
+    #[test]
+    fn greedy_best_first_may_return_longer_path_than_dijkstra() {
+        // S -> B -> G is shortest, but A looks closer to the goal, so greedy
+        // commits to S -> A -> C -> D -> G before ever expanding B
+        let neighbors = |n: char| -> Vec<char> {
+            match n {
+                'S' => vec!['A', 'B'],
+                'A' => vec!['C'],
+                'C' => vec!['D'],
+                'D' | 'B' => vec!['G'],
+                _ => vec![],
+            }
+        };
+        let h = |n: char| match n {
+            'G' => 0,
+            'A' => 1,
+            'C' => 2,
+            'D' => 3,
+            'B' => 5,
+            _ => 10,
+        };
+
+        let greedy = greedy_best_first('S', 'G', neighbors, h).unwrap();
+        assert_eq!(greedy, vec!['S', 'A', 'C', 'D', 'G']);
+
+        let weighted = |n| neighbors(n).into_iter().map(|m| (m, 1)).collect::<Vec<_>>();
+        assert_eq!(dijkstra_to('S', 'G', weighted), Some((2, vec!['S', 'B', 'G'])));
+    }
+
+    #[test]
+    fn greedy_best_first_unreachable_goal() {
+        let maze = ["S#G"];
+        assert_eq!(greedy_best_first((0, 0), (2, 0), maze_neighbors(&maze), |_| 0), None);
+    }
+
+    // ---- bfs_multi tests ----
+
+    #[test]
+    fn bfs_multi_gives_distance_to_nearest_source() {
+        // 0 - 1 - 2 - 3 - 4 - 5 - 6, sources at 0 and 5
+        let neighbors = |n: i32| -> Vec<i32> {
+            [n - 1, n + 1].into_iter().filter(|m| (0..=6).contains(m)).collect()
+        };
+
+        let dist = bfs_multi([0, 5], neighbors);
+
+        let expected: Vec<usize> = vec![0, 1, 2, 2, 1, 0, 1];
+        for (n, &d) in expected.iter().enumerate() {
+            assert_eq!(dist.get(&(n as i32)), Some(&d), "node {n}");
+        }
+    }
+
+    #[test]
+    fn bfs_multi_with_overlapping_starts() {
+        let neighbors = |n: i32| -> Vec<i32> { if n < 3 { vec![n + 1] } else { vec![] } };
+
+        let dist = bfs_multi([2, 0, 2], neighbors);
+
+        assert_eq!(dist.get(&0), Some(&0));
+        assert_eq!(dist.get(&2), Some(&0));
+        assert_eq!(dist.get(&1), Some(&1));
+        assert_eq!(dist.get(&3), Some(&1));
+    }
+
+    #[test]
+    fn bfs_multi_with_single_start_matches_bfs() {
+        let neighbors = |n: i32| -> Vec<i32> { if n < 5 { vec![n + 1, n + 2] } else { vec![] } };
+        assert_eq!(bfs_multi([0], neighbors), bfs(0, neighbors));
+    }
+
+    // ---- bfs01 tests ----
+
+    #[test]
+    fn bfs01_matches_dijkstra_on_zero_one_graph() {
+        // 0 -1-> 1 -1-> 2 -1-> 3
+        // 0 -0-> 4 -0-> 3,  4 -1-> 2
+        let neighbors = |n: i32| -> Vec<(i32, i64)> {
+            match n {
+                0 => vec![(1, 1), (4, 0)],
+                1 => vec![(2, 1)],
+                2 => vec![(3, 1)],
+                4 => vec![(3, 0), (2, 1)],
+                _ => vec![],
+            }
+        };
+
+        let dist = bfs01(0, neighbors);
+        let (expected, _) = dijkstra(0, neighbors);
+
+        assert_eq!(dist, expected);
+        assert_eq!(dist.get(&3), Some(&0));
+        assert_eq!(dist.get(&2), Some(&1));
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "must be 0 or 1")]
+    fn bfs01_rejects_other_weights_in_debug() {
+        let neighbors = |n: i32| -> Vec<(i32, i64)> { if n == 0 { vec![(1, 2)] } else { vec![] } };
+        bfs01(0, neighbors);
+    }
+
+    // ---- dijkstra_dial tests ----
+
+    #[test]
+    fn dijkstra_dial_matches_dijkstra() {
+        // pseudo-random graph on 0..30 with weights in 0..=3
+        let neighbors = |n: i32| -> Vec<(i32, i64)> {
+            (1..4)
+                .map(|k| ((n * 7 + k * 11) % 30, ((n + k) % 4) as i64))
+                .collect()
+        };
+
+        let dist = dijkstra_dial(0, 3, neighbors);
+        let (expected, _) = dijkstra(0, neighbors);
+
+        assert_eq!(dist, expected);
+    }
+
+    #[test]
+    #[should_panic(expected = "must be in 0..=3")]
+    fn dijkstra_dial_rejects_heavy_edges() {
+        let neighbors = |n: i32| -> Vec<(i32, i64)> { if n == 0 { vec![(1, 4)] } else { vec![] } };
+        dijkstra_dial(0, 3, neighbors);
+    }
+
+    #[test]
+    #[should_panic(expected = "must be non-negative")]
+    fn dijkstra_dial_rejects_negative_max_weight() {
+        dijkstra_dial(0, -1, |_: i32| -> Vec<(i32, i64)> { vec![] });
+    }
+
+    // ---- iddfs tests ----
+
+    #[test]
+    fn iddfs_finds_shortest_path_at_depth_three() {
+        // binary tree: n -> 2n, 2n + 1; 1 -> 2 -> 5 -> 11
+        let neighbors = |n: u32| -> Vec<u32> { vec![2 * n, 2 * n + 1] };
+
+        assert_eq!(iddfs(1, 11, 10, neighbors), Some(vec![1, 2, 5, 11]));
+        assert_eq!(iddfs(1, 1, 0, neighbors), Some(vec![1]));
+    }
+
+    #[test]
+    fn iddfs_prefers_shortest_path() {
+        // 0 -> 1 -> 2 -> 3 -> 4 and a shortcut 0 -> 9 -> 4; DFS order tries the long way first
+        let neighbors = |n: i32| -> Vec<i32> {
+            match n {
+                0 => vec![1, 9],
+                1 => vec![2],
+                2 => vec![3],
+                3 => vec![4],
+                9 => vec![4],
+                _ => vec![],
+            }
+        };
+        assert_eq!(iddfs(0, 4, 10, neighbors), Some(vec![0, 9, 4]));
+    }
+
+    #[test]
+    fn iddfs_respects_max_depth() {
+        let neighbors = |n: u32| -> Vec<u32> { vec![2 * n, 2 * n + 1] };
+        assert_eq!(iddfs(1, 11, 2, neighbors), None);
+        assert_eq!(iddfs(1, 11, 3, neighbors).map(|p| p.len() - 1), Some(3));
+    }
+
+    #[test]
+    fn iddfs_handles_cycles() {
+        // 0 <-> 1 <-> 2 <-> 3, undirected
+        let neighbors = |n: i32| -> Vec<i32> {
+            [n - 1, n + 1].into_iter().filter(|m| (0..4).contains(m)).collect()
+        };
+        assert_eq!(iddfs(0, 3, 5, neighbors), Some(vec![0, 1, 2, 3]));
+    }
+
+    // ---- dijkstra_tiebreak tests ----
+
+    #[test]
+    fn dijkstra_tiebreak_follows_tie_rule() {
+        // Diamond with equal costs: A -> B -> D and A -> C -> D.
+        // Whichever of B/C is expanded first claims D.
+        let neighbors = |n: char| -> Vec<(char, i64)> {
+            match n {
+                'A' => vec![('B', 1), ('C', 1)],
+                'B' => vec![('D', 1)],
+                'C' => vec![('D', 1)],
+                _ => vec![],
+            }
+        };
+
+        let (dist, prev) = dijkstra_tiebreak('A', neighbors, |a, b| a.cmp(b));
+        assert_eq!(dist.get(&'D'), Some(&2));
         assert_eq!(prev.get(&'D'), Some(&'B'));
 
-        // reconstruct the path A -> D
-        let path = reconstruct_path(&prev, 'D');
-        assert_eq!(path, vec!['A', 'B', 'D']);
+        let (dist, prev) = dijkstra_tiebreak('A', neighbors, |a, b| b.cmp(a));
+        assert_eq!(dist.get(&'D'), Some(&2));
+        assert_eq!(prev.get(&'D'), Some(&'C'));
     }
 
     #[test]
-    fn dijkstra_handles_outdated_queue_entries() {
-        // Graph where we first find a worse path to C, then a better one.
-        //
-        // A -> B (cost 5)
-        // A -> C (cost 10)
-        // B -> C (cost 1)  => better path A->B->C with cost 6
-        //
+    fn dijkstra_tiebreak_matches_dijkstra_distances() {
         let neighbors = |n: char| -> Vec<(char, i64)> {
             match n {
                 'A' => vec![('B', 5), ('C', 10)],
                 'B' => vec![('C', 1)],
-                'C' => vec![],
                 _ => vec![],
             }
         };
 
-        let (dist, prev) = dijkstra('A', neighbors);
+        let (dist, prev) = dijkstra_tiebreak('A', neighbors, |a, b| a.cmp(b));
+        assert_eq!(dist, dijkstra('A', neighbors).0);
+        assert_eq!(reconstruct_path(&prev, 'C'), vec!['A', 'B', 'C']);
+    }
 
-        assert_eq!(dist.get(&'A'), Some(&0));
-        assert_eq!(dist.get(&'B'), Some(&5));
-        assert_eq!(dist.get(&'C'), Some(&6));
+    #[test]
+    fn dijkstra_tiebreak_orders_zero_weight_arrivals() {
+        // A reaches E and B at 0; B reaches C and D at 0. With the smallest
+        // label first, expansion order is A, B, C, D, E, so D (not E) claims F.
+        let neighbors = |n: char| -> Vec<(char, i64)> {
+            match n {
+                'A' => vec![('E', 0), ('B', 0)],
+                'B' => vec![('D', 0), ('C', 0)],
+                'D' | 'E' => vec![('F', 1)],
+                _ => vec![],
+            }
+        };
 
-        assert_eq!(prev.get(&'B'), Some(&'A'));
-        assert_eq!(prev.get(&'C'), Some(&'B'));
+        let (dist, prev) = dijkstra_tiebreak('A', neighbors, |a, b| a.cmp(b));
+        assert_eq!(dist[&'F'], 1);
+        assert_eq!(prev[&'F'], 'D');
 
-        let path = reconstruct_path(&prev, 'C');
-        assert_eq!(path, vec!['A', 'B', 'C']);
+        let (_, prev) = dijkstra_tiebreak('A', neighbors, |a, b| b.cmp(a));
+        assert_eq!(prev[&'F'], 'E');
     }
 
     #[test]
-    fn dijkstra_with_isolated_start() {
-        let neighbors = |_n: i32| -> Vec<(i32, i64)> { vec![] };
-        let (dist, prev) = dijkstra(0, neighbors);
+    fn dijkstra_tiebreak_wide_unit_grid_matches_dijkstra() {
+        // a 200x200 open grid has frontiers hundreds of nodes wide
+        let neighbors = |(x, y): (i32, i32)| -> Vec<((i32, i32), i64)> {
+            [(x + 1, y), (x - 1, y), (x, y + 1), (x, y - 1)]
+                .into_iter()
+                .filter(|&(a, b)| (0..200).contains(&a) && (0..200).contains(&b))
+                .map(|p| (p, 1))
+                .collect()
+        };
+        let (dist, _) = dijkstra_tiebreak((0, 0), neighbors, |a, b| a.cmp(b));
+        assert_eq!(dist.len(), 40_000);
+        assert_eq!(dist[&(199, 199)], 398);
+    }
 
-        assert_eq!(dist.len(), 1);
-        assert_eq!(dist.get(&0), Some(&0));
-        assert!(prev.is_empty());
+    // ---- contract_corridors tests ----
+
+    #[test]
+    fn contract_corridors_on_line_with_branch() {
+        // 0 - 1 - 2 - 3 - 4
+        //         |
+        //         5 - 6 - 7
+        let adj: HashMap<i32, Vec<i32>> = HashMap::from([
+            (0, vec![1]),
+            (1, vec![0, 2]),
+            (2, vec![1, 3, 5]),
+            (3, vec![2, 4]),
+            (4, vec![3]),
+            (5, vec![2, 6]),
+            (6, vec![5, 7]),
+            (7, vec![6]),
+        ]);
+
+        let contracted = contract_corridors(adj.keys().copied(), |n| adj[&n].clone());
+
+        let mut junctions: Vec<i32> = contracted.keys().copied().collect();
+        junctions.sort();
+        assert_eq!(junctions, vec![0, 2, 4, 7]);
+
+        let mut from_hub = contracted[&2].clone();
+        from_hub.sort();
+        assert_eq!(from_hub, vec![(0, 2), (4, 2), (7, 3)]);
+        assert_eq!(contracted[&7], vec![(2, 3)]);
     }
 
-    // ---- reconstruct_path tests ----
+    #[test]
+    fn contract_corridors_drops_loops_back_to_same_junction() {
+        // 0 - 1 - 2 - 3 - 1 forms a loop hanging off junction 1
+        let adj: HashMap<i32, Vec<i32>> = HashMap::from([
+            (0, vec![1]),
+            (1, vec![0, 2, 3]),
+            (2, vec![1, 3]),
+            (3, vec![2, 1]),
+        ]);
+
+        let contracted = contract_corridors(adj.keys().copied(), |n| adj[&n].clone());
+        assert_eq!(contracted[&1], vec![(0, 1)]);
+        assert_eq!(contracted[&0], vec![(1, 1)]);
+    }
+
+    // ---- Memo tests ----
+
+    fn memo_fib(memo: &mut Memo<u64, u64>, n: u64) -> u64 {
+        memo.get_or_compute(n, |m| if n < 2 { n } else { memo_fib(m, n - 1) + memo_fib(m, n - 2) })
+    }
 
     #[test]
-    fn reconstruct_path_trivial() {
-        // No parents in map, path should just be [end]
-        let prev: HashMap<i32, i32> = HashMap::new();
-        let path = reconstruct_path(&prev, 99);
-        assert_eq!(path, vec![99]);
+    fn memo_fib_40_caches_intermediate_values() {
+        let mut memo = Memo::new();
+        assert!(memo.is_empty());
+
+        assert_eq!(memo_fib(&mut memo, 40), 102_334_155);
+        assert_eq!(memo.len(), 41); // fib(0) ..= fib(40)
+        assert_eq!(memo.get(&20), Some(&6765));
     }
 
     #[test]
-    fn reconstruct_path_longer_chain() {
-        // prev: 3 <- 2 <- 1
-        let mut prev = HashMap::new();
-        prev.insert(3, 2);
-        prev.insert(2, 1);
+    fn memo_does_not_recompute_cached_keys() {
+        let mut memo: Memo<&str, i32> = Memo::new();
+        let mut calls = 0;
 
-        let path = reconstruct_path(&prev, 3);
-        assert_eq!(path, vec![1, 2, 3]);
+        memo.get_or_compute("a", |_| {
+            calls += 1;
+            1
+        });
+        let v = memo.get_or_compute("a", |_| {
+            calls += 1;
+            2
+        });
+
+        assert_eq!(v, 1);
+        assert_eq!(calls, 1);
+    }
+
+    // ---- floyd_cycle tests ----
+
+    #[test]
+    fn floyd_cycle_with_tail_and_loop() {
+        // 0 -> 1 -> 2 -> 3 -> 4 -> 5 -> 2 ...: tail of 2, loop of 4
+        let next = |&n: &u32| if n == 5 { 2 } else { n + 1 };
+        assert_eq!(floyd_cycle(0, next), (2, 4));
+    }
+
+    #[test]
+    fn floyd_cycle_pure_loop_and_fixed_point() {
+        // x -> x^2 + 1 mod 10 from 3: 3, 0, 1, 2, 5, 6, 7, 0, ...
+        assert_eq!(floyd_cycle(3_u64, |&x| (x * x + 1) % 10), (1, 6));
+        // fixed point immediately
+        assert_eq!(floyd_cycle(7, |&x: &i32| x), (0, 1));
+        // pure loop from the start
+        assert_eq!(floyd_cycle(0, |&x: &i32| (x + 1) % 3), (0, 3));
+    }
+
+    // ---- solve_assignment tests ----
+
+    #[test]
+    fn solve_assignment_propagates_singletons() {
+        let candidates: HashMap<&str, HashSet<&str>> = HashMap::from([
+            ("dairy", HashSet::from(["mxmxvkd", "kfcds"])),
+            ("fish", HashSet::from(["mxmxvkd", "sqjhc"])),
+            ("soy", HashSet::from(["sqjhc", "fvjkl"])),
+            ("nuts", HashSet::from(["mxmxvkd"])),
+        ]);
+        // nuts fixes mxmxvkd -> dairy kfcds, fish sqjhc -> soy fvjkl
+        let expected = HashMap::from([
+            ("dairy", "kfcds"),
+            ("fish", "sqjhc"),
+            ("soy", "fvjkl"),
+            ("nuts", "mxmxvkd"),
+        ]);
+        assert_eq!(solve_assignment(&candidates), Some(expected));
+    }
+
+    #[test]
+    fn solve_assignment_stuck_or_impossible() {
+        let ambiguous = HashMap::from([(1, HashSet::from(['a', 'b'])), (2, HashSet::from(['a', 'b']))]);
+        assert_eq!(solve_assignment(&ambiguous), None);
+
+        let clash = HashMap::from([(1, HashSet::from(['a'])), (2, HashSet::from(['a']))]);
+        assert_eq!(solve_assignment(&clash), None);
+    }
+
+    // ---- max_flow tests ----
+
+    fn clrs_flow_network() -> Vec<(&'static str, &'static str, i64)> {
+        vec![
+            ("s", "v1", 16),
+            ("s", "v2", 13),
+            ("v1", "v3", 12),
+            ("v2", "v1", 4),
+            ("v2", "v4", 14),
+            ("v3", "v2", 9),
+            ("v3", "t", 20),
+            ("v4", "v3", 7),
+            ("v4", "t", 4),
+        ]
+    }
+
+    #[test]
+    fn max_flow_classic_network() {
+        assert_eq!(max_flow(&clrs_flow_network(), "s", "t"), 23);
+        assert_eq!(max_flow(&clrs_flow_network(), "t", "s"), 0);
+    }
+
+    #[test]
+    fn max_flow_sums_parallel_edges() {
+        let edges = [(0, 1, 2), (0, 1, 3), (1, 2, 10)];
+        assert_eq!(max_flow(&edges, 0, 2), 5);
+    }
+
+    #[test]
+    fn min_cut_capacity_equals_max_flow() {
+        let edges = clrs_flow_network();
+        let mut cut = min_cut(&edges, "s", "t");
+        cut.sort();
+        assert_eq!(cut, vec![("v1", "v3"), ("v4", "t"), ("v4", "v3")]);
     }
 }
@@ -0,0 +1,37 @@
+use std::fmt::Debug;
+
+/// Run `solver` on an example input and assert the answer matches `expected`.
+#[track_caller]
+pub fn check_example<T, F>(solver: F, input: &str, expected: T)
+where
+    T: PartialEq + Debug,
+    F: Fn(&str) -> T,
+{
+    let actual = solver(input);
+    assert!(
+        actual == expected,
+        "example check failed\n  expected: {expected:?}\n  actual:   {actual:?}"
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // ---- check_example tests ----
+
+    fn count_lines(s: &str) -> usize {
+        s.lines().count()
+    }
+
+    #[test]
+    fn check_example_passes_on_matching_answer() {
+        check_example(count_lines, "a\nb\nc", 3);
+    }
+
+    #[test]
+    #[should_panic(expected = "expected: 4")]
+    fn check_example_panics_on_mismatch() {
+        check_example(count_lines, "a\nb\nc", 4);
+    }
+}